@@ -145,7 +145,8 @@ fn main() -> ! {
     );
 
     // Initialize the display
-    let mut display = GC9A01A::new(spi, lcd_dc, lcd_cs, lcd_rst, false, LCD_WIDTH, LCD_HEIGHT);
+    let mut display =
+        GC9A01A::new(spi, lcd_dc, lcd_cs, lcd_rst, false, LCD_WIDTH, LCD_HEIGHT).unwrap();
     display.init(&mut delay).unwrap();
     display.set_orientation(&Orientation::Portrait).unwrap();
 
@@ -0,0 +1,360 @@
+//! Async driver for the GC9A01A display.
+//!
+//! This mirrors the blocking [`GC9A01A`](crate::GC9A01A) driver, but performs SPI transfers
+//! and delays through `embedded-hal-async` so that large transfers can yield to other tasks.
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::spi::SpiBus;
+
+use crate::{
+    fits_frame_memory, post_command_delay_ms, Error, Instruction, Orientation, DEFAULT_BUFFER_SIZE,
+    FRAME_MEMORY_SIZE, INIT_SEQUENCE, MADCTL_BGR, RESET_PULSE_US, RESET_SETTLE_MS,
+};
+
+/// Async driver for the GC9A01A display.
+pub struct GC9A01A<SPI, DC, CS, RST>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin,
+    CS: OutputPin<Error = DC::Error>,
+    RST: OutputPin<Error = DC::Error>,
+{
+    /// SPI interface.
+    spi: SPI,
+
+    /// Data/command pin.
+    dc: DC,
+
+    /// Chip select pin.
+    cs: CS,
+
+    /// Reset pin.
+    rst: RST,
+
+    /// Whether the display is RGB (true) or BGR (false).
+    rgb: bool,
+
+    /// Global image offset.
+    dx: u16,
+    dy: u16,
+    width: u32,
+    height: u32,
+}
+
+impl<SPI, DC, CS, RST> GC9A01A<SPI, DC, CS, RST>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin,
+    CS: OutputPin<Error = DC::Error>,
+    RST: OutputPin<Error = DC::Error>,
+{
+    /// Creates a new async driver instance that uses hardware SPI.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI interface.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    /// * `rst` - Reset pin.
+    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
+    /// * `width` - Width of the display.
+    /// * `height` - Height of the display.
+    ///
+    /// # Returns
+    ///
+    /// The driver, or `Error::OutOfBounds` if the width or height is 0 or larger
+    /// than the 240x240 frame memory.
+    pub fn new(
+        spi: SPI,
+        dc: DC,
+        cs: CS,
+        rst: RST,
+        rgb: bool,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error<DC::Error, SPI::Error>> {
+        if !fits_frame_memory(width, height) {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(GC9A01A {
+            spi,
+            dc,
+            cs,
+            rst,
+            rgb,
+            dx: 0,
+            dy: 0,
+            width,
+            height,
+        })
+    }
+
+    /// Initializes the display.
+    ///
+    /// Sends the same command sequence as the blocking driver after a hardware reset,
+    /// waiting after the commands listed in [`POST_COMMAND_DELAYS`](crate::POST_COMMAND_DELAYS).
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Async delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn init<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DC::Error, SPI::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        self.hard_reset(delay).await?;
+        for (command, params) in INIT_SEQUENCE {
+            if *command == Instruction::MadCtl as u8 && params.len() == 1 {
+                // Send the color order selected by the `rgb` flag, whatever the table says
+                let madctl = if self.rgb {
+                    params[0] & !MADCTL_BGR
+                } else {
+                    params[0] | MADCTL_BGR
+                };
+                self.write_command(*command, &[madctl]).await?;
+            } else {
+                self.write_command(*command, params).await?;
+            }
+            let ms = post_command_delay_ms(*command);
+            if ms > 0 {
+                delay.delay_ms(ms).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Performs a hard reset of the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Async delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn hard_reset<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DC::Error, SPI::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        self.rst.set_high().map_err(Error::Pin)?;
+        delay.delay_us(RESET_PULSE_US).await;
+        self.rst.set_low().map_err(Error::Pin)?;
+        delay.delay_us(RESET_PULSE_US).await;
+        self.rst.set_high().map_err(Error::Pin)?;
+        delay.delay_ms(RESET_SETTLE_MS).await;
+
+        Ok(())
+    }
+
+    /// Writes a command followed by optional parameters to the display.
+    async fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        // The command and its parameters are sent in a single CS-low transaction
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        self.spi.write(&[command]).await.map_err(Error::Spi)?;
+        if !params.is_empty() {
+            self.spi.flush().await.map_err(Error::Spi)?;
+            self.dc.set_high().map_err(Error::Pin)?;
+            self.spi.write(params).await.map_err(Error::Spi)?;
+        }
+        self.spi.flush().await.map_err(Error::Spi)?;
+        self.cs.set_high().map_err(Error::Pin)?;
+        Ok(())
+    }
+
+    /// Writes data to the display.
+    async fn write_data(&mut self, data: &[u8]) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        self.spi.write(data).await.map_err(Error::Spi)?;
+        self.spi.flush().await.map_err(Error::Spi)?;
+        self.cs.set_high().map_err(Error::Pin)?;
+        Ok(())
+    }
+
+    /// Writes a sequence of data words to the display through a small stack buffer.
+    async fn write_words_buffered<P: IntoIterator<Item = u16>>(
+        &mut self,
+        words: P,
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        let mut buffer = [0; DEFAULT_BUFFER_SIZE];
+        let mut index = 0;
+        for word in words {
+            let as_bytes = word.to_be_bytes();
+            buffer[index] = as_bytes[0];
+            buffer[index + 1] = as_bytes[1];
+            index += 2;
+            if index >= buffer.len() {
+                self.write_data(&buffer).await?;
+                index = 0;
+            }
+        }
+        // Only flush the bytes written since the last full buffer
+        if index > 0 {
+            self.write_data(&buffer[0..index]).await?;
+        }
+        Ok(())
+    }
+
+    /// Sets the orientation of the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `orientation` - Orientation to set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn set_orientation(
+        &mut self,
+        orientation: &Orientation,
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        if self.rgb {
+            self.write_command(Instruction::MadCtl as u8, &[*orientation as u8])
+                .await
+        } else {
+            self.write_command(
+                Instruction::MadCtl as u8,
+                &[*orientation as u8 | MADCTL_BGR],
+            )
+            .await
+        }
+    }
+
+    /// Sets the global offset of the displayed image.
+    ///
+    /// # Arguments
+    ///
+    /// * `dx` - Horizontal offset.
+    /// * `dy` - Vertical offset.
+    pub fn set_offset(&mut self, dx: u16, dy: u16) {
+        self.dx = dx;
+        self.dy = dy;
+    }
+
+    /// Sets the address window for the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the window does not lie within the display or its end lies before its start,
+    /// or if the offset moves it past the end of the controller's frame memory.
+    pub async fn set_address_window(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        if end_x < start_x
+            || end_y < start_y
+            || end_x as u32 >= self.width
+            || end_y as u32 >= self.height
+        {
+            return Err(Error::OutOfBounds);
+        }
+        if end_x as u32 + self.dx as u32 >= FRAME_MEMORY_SIZE
+            || end_y as u32 + self.dy as u32 >= FRAME_MEMORY_SIZE
+        {
+            return Err(Error::OutOfBounds);
+        }
+
+        let mut columns = [0; 4];
+        columns[..2].copy_from_slice(&(start_x + self.dx).to_be_bytes());
+        columns[2..].copy_from_slice(&(end_x + self.dx).to_be_bytes());
+        self.write_command(Instruction::CaSet as u8, &columns)
+            .await?;
+
+        let mut rows = [0; 4];
+        rows[..2].copy_from_slice(&(start_y + self.dy).to_be_bytes());
+        rows[2..].copy_from_slice(&(end_y + self.dy).to_be_bytes());
+        self.write_command(Instruction::RaSet as u8, &rows).await
+    }
+
+    /// Writes pixel colors into the current address window.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Pixel colors in RGB565 format.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn write_pixels<P: IntoIterator<Item = u16>>(
+        &mut self,
+        colors: P,
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.write_command(Instruction::RamWr as u8, &[]).await?;
+        self.write_words_buffered(colors).await
+    }
+
+    /// Draws a full-screen image from a slice of RGB565 data.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_data` - Image data to draw.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::WrongPixelCount` without sending anything unless `image_data` holds
+    /// two bytes for each pixel of the display.
+    pub async fn draw_image(
+        &mut self,
+        image_data: &[u8],
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        if image_data.len() != (self.width * self.height * 2) as usize {
+            return Err(Error::WrongPixelCount);
+        }
+        let width = self.width as u16;
+        let height = self.height as u16;
+
+        self.set_address_window(0, 0, width - 1, height - 1).await?;
+        self.write_command(Instruction::RamWr as u8, &[]).await?;
+        self.write_data(image_data).await
+    }
+
+    /// Displays the provided buffer on the screen.
+    ///
+    /// The whole buffer is sent in a single SPI transfer, which is where other tasks
+    /// get the chance to run.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Buffer to display.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub async fn show(&mut self, buffer: &[u8]) -> Result<(), Error<DC::Error, SPI::Error>> {
+        let width = self.width as u16;
+        let height = self.height as u16;
+
+        self.set_address_window(0, 0, width - 1, height - 1).await?;
+        self.write_command(Instruction::RamWr as u8, &[]).await?;
+        self.write_data(buffer).await
+    }
+}
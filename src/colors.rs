@@ -0,0 +1,119 @@
+//! Named RGB565 colors, color types and helpers for packing and blending colors.
+//!
+//! Colors are always packed as red in the high bits and blue in the low bits. If
+//! red and blue appear swapped on the panel, the driver's `rgb` flag does not match
+//! the panel's color order.
+
+use crate::ColorFormat;
+
+/// A color that the pixel writing methods can send to the display.
+///
+/// The driver asks the color for its bytes in the pixel format currently set with
+/// `set_color_format`, so colors of any depth can be drawn in either mode. It is
+/// implemented for `u16` RGB565 values, [`Rgb666`], and `Rgb565` from
+/// `embedded-graphics` when the `graphics` feature is enabled.
+pub trait Color: Copy {
+    /// Writes the color in the given pixel format into `out`, which holds exactly
+    /// `format.bytes_per_pixel()` bytes.
+    fn encode(self, format: ColorFormat, out: &mut [u8]);
+}
+
+impl Color for u16 {
+    fn encode(self, format: ColorFormat, out: &mut [u8]) {
+        format.encode(self, out);
+    }
+}
+
+/// An 18-bit color with six bits per channel.
+///
+/// Sent unchanged in [`ColorFormat::Rgb666`] mode, and reduced to RGB565 by
+/// dropping the low bit of red and blue otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Rgb666 {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl Rgb666 {
+    /// Creates a color from 6-bit channels; higher bits are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - Red channel, 0 to 63.
+    /// * `g` - Green channel, 0 to 63.
+    /// * `b` - Blue channel, 0 to 63.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: r & 0x3F,
+            g: g & 0x3F,
+            b: b & 0x3F,
+        }
+    }
+}
+
+impl Color for Rgb666 {
+    fn encode(self, format: ColorFormat, out: &mut [u8]) {
+        match format {
+            ColorFormat::Rgb565 => {
+                let color =
+                    ((self.r as u16 >> 1) << 11) | ((self.g as u16) << 5) | (self.b as u16 >> 1);
+                out.copy_from_slice(&color.to_be_bytes());
+            }
+            ColorFormat::Rgb666 => {
+                out[0] = self.r << 2;
+                out[1] = self.g << 2;
+                out[2] = self.b << 2;
+            }
+        }
+    }
+}
+
+/// Packs a 24-bit color into RGB565 by keeping the top 5, 6 and 5 bits of the red,
+/// green and blue channels.
+///
+/// # Arguments
+///
+/// * `r` - Red channel.
+/// * `g` - Green channel.
+/// * `b` - Blue channel.
+pub const fn rgb565(r: u8, g: u8, b: u8) -> u16 {
+    ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3)
+}
+
+/// Blends two RGB565 colors, for compositing semi-transparent overlays in a
+/// frame buffer before it is sent.
+///
+/// Each channel is mixed separately in its own 5- or 6-bit range and rounded to
+/// the nearest value, so an `alpha` of 255 gives `fg` and 0 gives `bg` exactly.
+///
+/// # Arguments
+///
+/// * `fg` - Overlay color.
+/// * `bg` - Color underneath.
+/// * `alpha` - Opacity of `fg`, from 0 (transparent) to 255 (opaque).
+pub const fn blend565(fg: u16, bg: u16, alpha: u8) -> u16 {
+    blend_channel(fg, bg, alpha, 11, 0x1F)
+        | blend_channel(fg, bg, alpha, 5, 0x3F)
+        | blend_channel(fg, bg, alpha, 0, 0x1F)
+}
+
+/// Blends the channel at `shift` of two packed colors, rounding to nearest.
+const fn blend_channel(fg: u16, bg: u16, alpha: u8, shift: u32, mask: u32) -> u16 {
+    let a = alpha as u32;
+    let f = (fg as u32 >> shift) & mask;
+    let b = (bg as u32 >> shift) & mask;
+    (((f * a + b * (255 - a) + 127) / 255) << shift) as u16
+}
+
+pub const BLACK: u16 = rgb565(0x00, 0x00, 0x00);
+pub const WHITE: u16 = rgb565(0xFF, 0xFF, 0xFF);
+pub const RED: u16 = rgb565(0xFF, 0x00, 0x00);
+pub const GREEN: u16 = rgb565(0x00, 0xFF, 0x00);
+pub const BLUE: u16 = rgb565(0x00, 0x00, 0xFF);
+pub const YELLOW: u16 = rgb565(0xFF, 0xFF, 0x00);
+pub const CYAN: u16 = rgb565(0x00, 0xFF, 0xFF);
+pub const MAGENTA: u16 = rgb565(0xFF, 0x00, 0xFF);
+pub const ORANGE: u16 = rgb565(0xFF, 0xA5, 0x00);
+pub const GRAY: u16 = rgb565(0x80, 0x80, 0x80);
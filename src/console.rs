@@ -0,0 +1,362 @@
+//! Scrolling text console drawn with a built-in bitmap font.
+
+use core::fmt;
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{Color, DisplayInterface, Error, FRAME_MEMORY_SIZE, GC9A01A};
+
+/// Width of a character cell in pixels, including one column of spacing.
+const CELL_WIDTH: u16 = 6;
+
+/// Height of a character cell in pixels, including one row of spacing.
+const CELL_HEIGHT: u16 = 8;
+
+/// Most columns that fit across the frame memory.
+const MAX_COLUMNS: usize = (FRAME_MEMORY_SIZE / CELL_WIDTH as u32) as usize;
+
+/// Most rows that fit down the frame memory.
+const MAX_ROWS: usize = (FRAME_MEMORY_SIZE / CELL_HEIGHT as u32) as usize;
+
+/// A text console that writes lines of characters to the display.
+///
+/// Characters are drawn with a built-in 5x7 ASCII font in 6x8 pixel cells using
+/// `blit_mono`, so each one is a single small window write. The cursor advances
+/// after every character, wraps to the next row at the right edge of the console
+/// area, and moves to the start of the next row on `'\n'`. Writing past the last
+/// row scrolls the text up by one row; only the cells whose character changes are
+/// redrawn. Characters outside printable ASCII are drawn as `'?'`.
+///
+/// The console keeps a copy of its text so it can scroll without reading back
+/// from the display, and assumes the area starts out blank; call `clear` first if
+/// it does not. Because the panel is round, the corners of the full display are not
+/// visible, so a smaller area set with `with_area` is often easier to read.
+///
+/// Text is written through [`core::fmt::Write`], so `write!` and `writeln!` work.
+/// A display error is reported as [`core::fmt::Error`]; the driver error itself is
+/// available from the inherent methods, which return `Result<(), Error>`.
+pub struct TextConsole<'d, DI, RST, BL, C, const BUF: usize>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    C: Color,
+{
+    display: &'d mut GC9A01A<DI, RST, BL, BUF>,
+    fg: C,
+    bg: C,
+    x: u16,
+    y: u16,
+    columns: u16,
+    rows: u16,
+    column: u16,
+    row: u16,
+    text: [[u8; MAX_COLUMNS]; MAX_ROWS],
+}
+
+impl<'d, DI, RST, BL, C, const BUF: usize> TextConsole<'d, DI, RST, BL, C, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    C: Color,
+{
+    /// Creates a console covering the whole display, with the cursor in the
+    /// top-left cell.
+    ///
+    /// Nothing is sent to the display until text is written or `clear` is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `display` - Display to draw on.
+    /// * `fg` - Color of the characters.
+    /// * `bg` - Color behind the characters.
+    ///
+    /// # Returns
+    ///
+    /// A new `TextConsole` instance, or `Error::OutOfBounds` if the display is
+    /// narrower or shorter than one character cell.
+    pub fn new(
+        display: &'d mut GC9A01A<DI, RST, BL, BUF>,
+        fg: C,
+        bg: C,
+    ) -> Result<Self, Error<DI::PinError, DI::BusError>> {
+        let columns = (display.width / CELL_WIDTH as u32) as u16;
+        let rows = (display.height / CELL_HEIGHT as u32) as u16;
+        if columns == 0 || rows == 0 {
+            return Err(Error::OutOfBounds);
+        }
+        Ok(Self {
+            display,
+            fg,
+            bg,
+            x: 0,
+            y: 0,
+            columns,
+            rows,
+            column: 0,
+            row: 0,
+            text: [[b' '; MAX_COLUMNS]; MAX_ROWS],
+        })
+    }
+
+    /// Limits the console to an area of the display and moves the cursor to its
+    /// top-left cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the top-left corner of the area.
+    /// * `y` - Y-coordinate of the top-left corner of the area.
+    /// * `columns` - Number of characters per row.
+    /// * `rows` - Number of rows.
+    ///
+    /// # Returns
+    ///
+    /// The console with the new area, or `Error::OutOfBounds` if the area is empty or
+    /// does not fit on the display.
+    pub fn with_area(
+        mut self,
+        x: u16,
+        y: u16,
+        columns: u16,
+        rows: u16,
+    ) -> Result<Self, Error<DI::PinError, DI::BusError>> {
+        if columns == 0
+            || rows == 0
+            || x as u32 + columns as u32 * CELL_WIDTH as u32 > self.display.width
+            || y as u32 + rows as u32 * CELL_HEIGHT as u32 > self.display.height
+        {
+            return Err(Error::OutOfBounds);
+        }
+        self.x = x;
+        self.y = y;
+        self.columns = columns;
+        self.rows = rows;
+        self.column = 0;
+        self.row = 0;
+        Ok(self)
+    }
+
+    /// Returns the cursor position as `(column, row)`.
+    pub fn cursor(&self) -> (u16, u16) {
+        (self.column, self.row)
+    }
+
+    /// Moves the cursor to a cell of the console.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Column of the cell, clamped to the last column.
+    /// * `row` - Row of the cell, clamped to the last row.
+    pub fn set_cursor(&mut self, column: u16, row: u16) {
+        self.column = column.min(self.columns - 1);
+        self.row = row.min(self.rows - 1);
+    }
+
+    /// Fills the console area with the background color, forgets its text and
+    /// moves the cursor to the top-left cell.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn clear(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.text = [[b' '; MAX_COLUMNS]; MAX_ROWS];
+        self.column = 0;
+        self.row = 0;
+        self.display.fill_rect(
+            self.x,
+            self.y,
+            self.columns as u32 * CELL_WIDTH as u32,
+            self.rows as u32 * CELL_HEIGHT as u32,
+            self.bg,
+        )
+    }
+
+    /// Writes a string at the cursor, wrapping and scrolling as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to write. `'\n'` starts a new row and `'\r'` returns to the
+    ///   first column.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn write_text(&mut self, text: &str) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        for c in text.chars() {
+            match c {
+                '\n' => self.new_line()?,
+                '\r' => self.column = 0,
+                _ => {
+                    if self.column == self.columns {
+                        self.new_line()?;
+                    }
+                    let byte = if c.is_ascii_graphic() || c == ' ' {
+                        c as u8
+                    } else {
+                        b'?'
+                    };
+                    self.text[self.row as usize][self.column as usize] = byte;
+                    self.draw_cell(self.column, self.row)?;
+                    self.column += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor to the start of the next row, scrolling if it is on the
+    /// last row.
+    fn new_line(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.column = 0;
+        if self.row + 1 < self.rows {
+            self.row += 1;
+            return Ok(());
+        }
+
+        for row in 0..self.rows as usize {
+            for column in 0..self.columns as usize {
+                let next = if row + 1 < self.rows as usize {
+                    self.text[row + 1][column]
+                } else {
+                    b' '
+                };
+                if self.text[row][column] != next {
+                    self.text[row][column] = next;
+                    self.draw_cell(column as u16, row as u16)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws the stored character of a cell.
+    fn draw_cell(
+        &mut self,
+        column: u16,
+        row: u16,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let byte = self.text[row as usize][column as usize];
+        self.display.blit_mono(
+            self.x + column * CELL_WIDTH,
+            self.y + row * CELL_HEIGHT,
+            CELL_WIDTH as u32,
+            CELL_HEIGHT as u32,
+            &FONT[(byte - b' ') as usize],
+            self.fg,
+            self.bg,
+        )
+    }
+}
+
+impl<DI, RST, BL, C, const BUF: usize> fmt::Write for TextConsole<'_, DI, RST, BL, C, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    C: Color,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_text(s).map_err(|_| fmt::Error)
+    }
+}
+
+/// Glyphs for the printable ASCII characters from `' '` to `'~'`.
+///
+/// Each glyph is eight rows of one byte, most significant bit on the left. The
+/// character uses the top five bits of the first seven rows; the rest is spacing.
+#[rustfmt::skip]
+const FONT: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x20, 0x00], // !
+    [0x50, 0x50, 0x50, 0x00, 0x00, 0x00, 0x00, 0x00], // "
+    [0x50, 0x50, 0xF8, 0x50, 0xF8, 0x50, 0x50, 0x00], // #
+    [0x20, 0x78, 0xA0, 0x70, 0x28, 0xF0, 0x20, 0x00], // $
+    [0xC0, 0xC8, 0x10, 0x20, 0x40, 0x98, 0x18, 0x00], // %
+    [0x60, 0x90, 0xA0, 0x40, 0xA8, 0x90, 0x68, 0x00], // &
+    [0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00], // '
+    [0x10, 0x20, 0x40, 0x40, 0x40, 0x20, 0x10, 0x00], // (
+    [0x40, 0x20, 0x10, 0x10, 0x10, 0x20, 0x40, 0x00], // )
+    [0x00, 0x20, 0xA8, 0x70, 0xA8, 0x20, 0x00, 0x00], // *
+    [0x00, 0x20, 0x20, 0xF8, 0x20, 0x20, 0x00, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x60, 0x20, 0x40, 0x00], // ,
+    [0x00, 0x00, 0x00, 0xF8, 0x00, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x60, 0x60, 0x00], // .
+    [0x00, 0x08, 0x10, 0x20, 0x40, 0x80, 0x00, 0x00], // /
+    [0x70, 0x88, 0x98, 0xA8, 0xC8, 0x88, 0x70, 0x00], // 0
+    [0x20, 0x60, 0x20, 0x20, 0x20, 0x20, 0x70, 0x00], // 1
+    [0x70, 0x88, 0x08, 0x10, 0x20, 0x40, 0xF8, 0x00], // 2
+    [0xF8, 0x10, 0x20, 0x10, 0x08, 0x88, 0x70, 0x00], // 3
+    [0x10, 0x30, 0x50, 0x90, 0xF8, 0x10, 0x10, 0x00], // 4
+    [0xF8, 0x80, 0xF0, 0x08, 0x08, 0x88, 0x70, 0x00], // 5
+    [0x30, 0x40, 0x80, 0xF0, 0x88, 0x88, 0x70, 0x00], // 6
+    [0xF8, 0x08, 0x10, 0x20, 0x40, 0x40, 0x40, 0x00], // 7
+    [0x70, 0x88, 0x88, 0x70, 0x88, 0x88, 0x70, 0x00], // 8
+    [0x70, 0x88, 0x88, 0x78, 0x08, 0x10, 0x60, 0x00], // 9
+    [0x00, 0x60, 0x60, 0x00, 0x60, 0x60, 0x00, 0x00], // :
+    [0x00, 0x60, 0x60, 0x00, 0x60, 0x20, 0x40, 0x00], // ;
+    [0x10, 0x20, 0x40, 0x80, 0x40, 0x20, 0x10, 0x00], // <
+    [0x00, 0x00, 0xF8, 0x00, 0xF8, 0x00, 0x00, 0x00], // =
+    [0x40, 0x20, 0x10, 0x08, 0x10, 0x20, 0x40, 0x00], // >
+    [0x70, 0x88, 0x08, 0x10, 0x20, 0x00, 0x20, 0x00], // ?
+    [0x70, 0x88, 0x08, 0x68, 0xA8, 0xA8, 0x70, 0x00], // @
+    [0x70, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x88, 0x00], // A
+    [0xF0, 0x88, 0x88, 0xF0, 0x88, 0x88, 0xF0, 0x00], // B
+    [0x70, 0x88, 0x80, 0x80, 0x80, 0x88, 0x70, 0x00], // C
+    [0xE0, 0x90, 0x88, 0x88, 0x88, 0x90, 0xE0, 0x00], // D
+    [0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0xF8, 0x00], // E
+    [0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0x80, 0x00], // F
+    [0x70, 0x88, 0x80, 0xB8, 0x88, 0x88, 0x78, 0x00], // G
+    [0x88, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x88, 0x00], // H
+    [0x70, 0x20, 0x20, 0x20, 0x20, 0x20, 0x70, 0x00], // I
+    [0x38, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60, 0x00], // J
+    [0x88, 0x90, 0xA0, 0xC0, 0xA0, 0x90, 0x88, 0x00], // K
+    [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xF8, 0x00], // L
+    [0x88, 0xD8, 0xA8, 0xA8, 0x88, 0x88, 0x88, 0x00], // M
+    [0x88, 0x88, 0xC8, 0xA8, 0x98, 0x88, 0x88, 0x00], // N
+    [0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x00], // O
+    [0xF0, 0x88, 0x88, 0xF0, 0x80, 0x80, 0x80, 0x00], // P
+    [0x70, 0x88, 0x88, 0x88, 0xA8, 0x90, 0x68, 0x00], // Q
+    [0xF0, 0x88, 0x88, 0xF0, 0xA0, 0x90, 0x88, 0x00], // R
+    [0x78, 0x80, 0x80, 0x70, 0x08, 0x08, 0xF0, 0x00], // S
+    [0xF8, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00], // T
+    [0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x00], // U
+    [0x88, 0x88, 0x88, 0x88, 0x88, 0x50, 0x20, 0x00], // V
+    [0x88, 0x88, 0x88, 0xA8, 0xA8, 0xA8, 0x50, 0x00], // W
+    [0x88, 0x88, 0x50, 0x20, 0x50, 0x88, 0x88, 0x00], // X
+    [0x88, 0x88, 0x88, 0x50, 0x20, 0x20, 0x20, 0x00], // Y
+    [0xF8, 0x08, 0x10, 0x20, 0x40, 0x80, 0xF8, 0x00], // Z
+    [0x70, 0x40, 0x40, 0x40, 0x40, 0x40, 0x70, 0x00], // [
+    [0x00, 0x80, 0x40, 0x20, 0x10, 0x08, 0x00, 0x00], // \
+    [0x70, 0x10, 0x10, 0x10, 0x10, 0x10, 0x70, 0x00], // ]
+    [0x20, 0x50, 0x88, 0x00, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF8, 0x00], // _
+    [0x40, 0x20, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x70, 0x08, 0x78, 0x88, 0x78, 0x00], // a
+    [0x80, 0x80, 0xB0, 0xC8, 0x88, 0x88, 0xF0, 0x00], // b
+    [0x00, 0x00, 0x70, 0x80, 0x80, 0x88, 0x70, 0x00], // c
+    [0x08, 0x08, 0x68, 0x98, 0x88, 0x88, 0x78, 0x00], // d
+    [0x00, 0x00, 0x70, 0x88, 0xF8, 0x80, 0x70, 0x00], // e
+    [0x30, 0x48, 0x40, 0xE0, 0x40, 0x40, 0x40, 0x00], // f
+    [0x00, 0x78, 0x88, 0x88, 0x78, 0x08, 0x70, 0x00], // g
+    [0x80, 0x80, 0xB0, 0xC8, 0x88, 0x88, 0x88, 0x00], // h
+    [0x20, 0x00, 0x60, 0x20, 0x20, 0x20, 0x70, 0x00], // i
+    [0x10, 0x00, 0x30, 0x10, 0x10, 0x90, 0x60, 0x00], // j
+    [0x80, 0x80, 0x90, 0xA0, 0xC0, 0xA0, 0x90, 0x00], // k
+    [0x60, 0x20, 0x20, 0x20, 0x20, 0x20, 0x70, 0x00], // l
+    [0x00, 0x00, 0xD0, 0xA8, 0xA8, 0x88, 0x88, 0x00], // m
+    [0x00, 0x00, 0xB0, 0xC8, 0x88, 0x88, 0x88, 0x00], // n
+    [0x00, 0x00, 0x70, 0x88, 0x88, 0x88, 0x70, 0x00], // o
+    [0x00, 0x00, 0xF0, 0x88, 0xF0, 0x80, 0x80, 0x00], // p
+    [0x00, 0x00, 0x68, 0x98, 0x78, 0x08, 0x08, 0x00], // q
+    [0x00, 0x00, 0xB0, 0xC8, 0x80, 0x80, 0x80, 0x00], // r
+    [0x00, 0x00, 0x70, 0x80, 0x70, 0x08, 0xF0, 0x00], // s
+    [0x40, 0x40, 0xE0, 0x40, 0x40, 0x48, 0x30, 0x00], // t
+    [0x00, 0x00, 0x88, 0x88, 0x88, 0x98, 0x68, 0x00], // u
+    [0x00, 0x00, 0x88, 0x88, 0x88, 0x50, 0x20, 0x00], // v
+    [0x00, 0x00, 0x88, 0x88, 0xA8, 0xA8, 0x50, 0x00], // w
+    [0x00, 0x00, 0x88, 0x50, 0x20, 0x50, 0x88, 0x00], // x
+    [0x00, 0x00, 0x88, 0x88, 0x78, 0x08, 0x70, 0x00], // y
+    [0x00, 0x00, 0xF8, 0x10, 0x20, 0x40, 0xF8, 0x00], // z
+    [0x10, 0x20, 0x20, 0x40, 0x20, 0x20, 0x10, 0x00], // {
+    [0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00], // |
+    [0x40, 0x20, 0x20, 0x10, 0x20, 0x20, 0x40, 0x00], // }
+    [0x00, 0x00, 0x40, 0xA8, 0x10, 0x00, 0x00, 0x00], // ~
+];
@@ -0,0 +1,96 @@
+//! Fixed-size in-RAM frame buffer for flicker-free drawing.
+
+use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+use embedded_hal::digital::OutputPin;
+
+use crate::{DisplayInterface, Error, Instruction, GC9A01A};
+
+/// A frame buffer holding `W` x `H` RGB565 pixels in a fixed-size array.
+///
+/// Drawing goes to RAM only; call [`GC9A01A::flush`] to push the finished frame to
+/// the panel in one pass. The buffer takes `W * H * 2` bytes, which is 115,200 bytes
+/// for a 240x240 display, so it is usually placed in a `static` rather than on the
+/// stack, and it may not fit on small microcontrollers at all.
+pub struct StaticFrameBuffer<const W: usize, const H: usize> {
+    pixels: [[u16; W]; H],
+}
+
+impl<const W: usize, const H: usize> StaticFrameBuffer<W, H> {
+    /// Creates a new frame buffer filled with black.
+    pub const fn new() -> Self {
+        Self {
+            pixels: [[0; W]; H],
+        }
+    }
+
+    /// Returns the pixel rows in RGB565 format.
+    pub fn pixels(&self) -> &[[u16; W]; H] {
+        &self.pixels
+    }
+}
+
+impl<const W: usize, const H: usize> Default for StaticFrameBuffer<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> DrawTarget for StaticFrameBuffer<W, H> {
+    type Color = Rgb565;
+    type Error = ();
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(coord, color) in pixels {
+            if coord.x >= 0 && (coord.x as usize) < W && coord.y >= 0 && (coord.y as usize) < H {
+                self.pixels[coord.y as usize][coord.x as usize] = color.into_storage();
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels = [[color.into_storage(); W]; H];
+        Ok(())
+    }
+}
+
+impl<const W: usize, const H: usize> OriginDimensions for StaticFrameBuffer<W, H> {
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> GC9A01A<DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Pushes a whole frame buffer to the display.
+    ///
+    /// The frame is drawn from the top-left corner of the display and streamed
+    /// through the transfer buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `framebuffer` - Frame buffer to display.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the frame buffer is larger than the display.
+    pub fn flush<const W: usize, const H: usize>(
+        &mut self,
+        framebuffer: &StaticFrameBuffer<W, H>,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if W == 0 || H == 0 {
+            return Ok(());
+        }
+
+        self.set_address_window(0, 0, W as u16 - 1, H as u16 - 1)?;
+        self.write_command(Instruction::RamWr as u8, &[])?;
+        self.write_colors_buffered(framebuffer.pixels.iter().flatten().copied())
+    }
+}
@@ -0,0 +1,672 @@
+//! `embedded-graphics` integration for the GC9A01A driver.
+
+use embedded_graphics_core::image::ImageDrawable;
+use embedded_graphics_core::pixelcolor::raw::RawU16;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*};
+use embedded_hal::digital::OutputPin;
+
+use crate::{Color, ColorFormat, DisplayInterface, Error, Instruction, Region, GC9A01A};
+
+impl Color for Rgb565 {
+    fn encode(self, format: ColorFormat, out: &mut [u8]) {
+        self.into_storage().encode(format, out);
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> GC9A01A<DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Clears the whole display with a single color.
+    ///
+    /// Same as `clear_screen`: the color is converted once and sent in chunks, so no
+    /// frame buffer is needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color to fill the display with.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn clear(&mut self, color: Rgb565) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.clear_screen(color)
+    }
+
+    /// Sets the color of a single pixel to an `Rgb565` color.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate.
+    /// * `y` - Y-coordinate.
+    /// * `color` - Color of the pixel.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the pixel lies outside the display.
+    pub fn write_pixel_rgb565(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: Rgb565,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_pixel(x, y, color)
+    }
+
+    /// Sets the pixels in a rectangular window to a sequence of `Rgb565` colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    /// * `colors` - Pixel colors, row by row.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_pixels_rgb565<P: IntoIterator<Item = Rgb565>>(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+        colors: P,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.set_pixels(start_x, start_y, end_x, end_y, colors)
+    }
+
+    /// Fills a rectangle with a single `Rgb565` color, clipped to the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the top-left corner.
+    /// * `y` - Y-coordinate of the top-left corner.
+    /// * `width` - Width of the rectangle.
+    /// * `height` - Height of the rectangle.
+    /// * `color` - Fill color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn fill_rect_rgb565(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u32,
+        height: u32,
+        color: Rgb565,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.fill_rect(x, y, width, height, color)
+    }
+
+    /// Fills a rectangle with a single color, clipped to the display.
+    ///
+    /// The rectangle may lie partly or wholly outside the display, including at
+    /// negative coordinates; only the visible part is filled.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - Rectangle to fill.
+    /// * `color` - Fill color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Nothing is sent if no part
+    /// of the rectangle is visible.
+    pub fn fill<C: Color>(
+        &mut self,
+        area: &Rectangle,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.fill_rect_clipped(
+            area.top_left.x,
+            area.top_left.y,
+            area.size.width,
+            area.size.height,
+            color,
+        )
+    }
+
+    /// Draws an `Rgb565` image, such as an embedded-graphics `ImageRaw`, at a position.
+    ///
+    /// The image supplies its own dimensions and decodes its pixel data in its own
+    /// byte order, so no slice or size bookkeeping is needed. Fully visible images are
+    /// sent in a single window; parts past the display edge are clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - Image to draw.
+    /// * `top_left` - Position of the top-left corner of the image.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn draw_image_raw<I>(
+        &mut self,
+        image: &I,
+        top_left: Point,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        I: ImageDrawable<Color = Rgb565>,
+    {
+        image.draw(&mut Translated {
+            display: self,
+            offset: top_left,
+        })
+    }
+
+    /// Updates only the changed parts of the display from a full-screen buffer.
+    ///
+    /// The rectangles are clipped to the display and overlapping ones are merged
+    /// before sending, so no pixel is transmitted twice. Each remaining rectangle is
+    /// sent as with `show_region`.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Full-screen buffer of pixel data in RGB565 format.
+    /// * `dirty` - Rectangles that changed since the last update.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn show_dirty(
+        &mut self,
+        buffer: &[u8],
+        dirty: &[Rectangle],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let bounds = self.bounding_box();
+        let mut merged = [Rectangle::zero(); MAX_DIRTY_RECTS];
+        let mut count = 0;
+
+        for rect in dirty {
+            let rect = rect.intersection(&bounds);
+            if rect.is_zero_sized() {
+                continue;
+            }
+            // Once all slots are used, grow the last rectangle to cover the rest
+            if count < MAX_DIRTY_RECTS {
+                merged[count] = rect;
+                count += 1;
+            } else {
+                merged[count - 1] = union(&merged[count - 1], &rect);
+            }
+        }
+
+        let count = merge_overlapping(&mut merged[..count]);
+        for rect in &merged[..count] {
+            self.show_region(
+                buffer,
+                rect.top_left.x as u16,
+                rect.top_left.y as u16,
+                rect.size.width,
+                rect.size.height,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Pushes the part of a frame buffer that changed since the last flush.
+    ///
+    /// Only the bounding box of everything drawn into the frame buffer since the
+    /// previous flush is sent, as with `show_dirty`, so plain `embedded-graphics`
+    /// drawing code gets partial updates without tracking regions itself. The dirty
+    /// area is cleared once it has been sent. The frame buffer holds RGB565, so in
+    /// any other color format each pixel is converted as it is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `framebuffer` - Frame buffer with the dimensions of the display.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Nothing is sent if nothing
+    /// was drawn. Returns `Error::OutOfBounds` if the frame buffer is not as wide as
+    /// the display.
+    pub fn flush_framebuffer(
+        &mut self,
+        framebuffer: &mut FrameBuffer<'_>,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let Some(area) = framebuffer.dirty_area() else {
+            return Ok(());
+        };
+        if framebuffer.width != self.width {
+            return Err(Error::OutOfBounds);
+        }
+
+        if self.color_format == ColorFormat::Rgb565 {
+            self.show_dirty(framebuffer.get_buffer(), &[area])?;
+        } else {
+            let (x, y) = (area.top_left.x as usize, area.top_left.y as usize);
+            let (width, height) = (area.size.width as usize, area.size.height as usize);
+            let pixels = framebuffer
+                .get_buffer()
+                .chunks_exact(framebuffer.width as usize * 2)
+                .skip(y)
+                .take(height)
+                .flat_map(|row| row[x * 2..(x + width) * 2].chunks_exact(2))
+                .map(|pixel| u16::from_be_bytes([pixel[0], pixel[1]]));
+            self.set_pixels(
+                x as u16,
+                y as u16,
+                (x + width - 1) as u16,
+                (y + height - 1) as u16,
+                pixels,
+            )?;
+        }
+        framebuffer.mark_clean();
+        Ok(())
+    }
+}
+
+/// Number of separate rectangles `show_dirty` keeps after merging.
+const MAX_DIRTY_RECTS: usize = 16;
+
+/// Returns the smallest rectangle covering both non-empty rectangles.
+fn union(a: &Rectangle, b: &Rectangle) -> Rectangle {
+    let (a_end, b_end) = (a.top_left + a.size, b.top_left + b.size);
+    let top_left = a.top_left.component_min(b.top_left);
+    let bottom_right = a_end.component_max(b_end);
+    Rectangle::with_corners(top_left, bottom_right - Point::new(1, 1))
+}
+
+/// Merges overlapping rectangles until none overlap, returning the number left.
+fn merge_overlapping(rects: &mut [Rectangle]) -> usize {
+    let mut count = rects.len();
+    let mut i = 0;
+    while i < count {
+        let overlap = (i + 1..count).find(|&j| !rects[i].intersection(&rects[j]).is_zero_sized());
+        match overlap {
+            Some(j) => {
+                rects[i] = union(&rects[i], &rects[j]);
+                rects[j] = rects[count - 1];
+                count -= 1;
+                // The grown rectangle may now overlap ones already checked
+                i = 0;
+            }
+            None => i += 1,
+        }
+    }
+    count
+}
+
+// Implementing the DrawTarget trait for the GC9A01A display driver
+impl<DI, RST, BL, const BUF: usize> DrawTarget for GC9A01A<DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    type Color = Rgb565;
+    type Error = Error<DI::PinError, DI::BusError>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.with_transfer_buffer(|display, buffer| {
+            let bytes_per_pixel = display.color_format.bytes_per_pixel();
+            let mut index = 0;
+            // Position a pixel must have to continue the current run
+            let mut next: Option<Point> = None;
+
+            for Pixel(coord, color) in pixels.into_iter() {
+                // Only draw pixels that would be on screen
+                if coord.x < 0
+                    || coord.y < 0
+                    || coord.x >= display.width as i32
+                    || coord.y >= display.height as i32
+                {
+                    continue;
+                }
+
+                // Open a new window running to the end of the row whenever the run is broken
+                if next != Some(coord) {
+                    if index > 0 {
+                        display.write_data(&buffer[..index])?;
+                        index = 0;
+                    }
+                    let (x, y) = (coord.x as u16, coord.y as u16);
+                    display.set_address_window(x, y, display.width as u16 - 1, y)?;
+                    display.write_command(Instruction::RamWr as u8, &[])?;
+                }
+
+                if index + bytes_per_pixel > buffer.len() {
+                    display.write_data(&buffer[..index])?;
+                    index = 0;
+                }
+                display.encode_pixel(color, &mut buffer[index..index + bytes_per_pixel]);
+                index += bytes_per_pixel;
+
+                next = Some(Point::new(coord.x + 1, coord.y));
+            }
+
+            if index > 0 {
+                display.write_data(&buffer[..index])?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        // Areas that are partially off screen need per-pixel clipping
+        if area.intersection(&self.bounding_box()) != *area {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(pos, color)| Pixel(pos, color)),
+            );
+        }
+
+        match area.bottom_right() {
+            Some(bottom_right) => self.set_pixels(
+                area.top_left.x as u16,
+                area.top_left.y as u16,
+                bottom_right.x as u16,
+                bottom_right.y as u16,
+                colors.into_iter().map(|color| color.into_storage()),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+
+        match area.bottom_right() {
+            Some(bottom_right) => self.fill_window(
+                area.top_left.x as u16,
+                area.top_left.y as u16,
+                bottom_right.x as u16,
+                bottom_right.y as u16,
+                color,
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Draw target that moves everything drawn by `offset` before passing it to the display.
+struct Translated<'d, DI, RST, BL, const BUF: usize>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    display: &'d mut GC9A01A<DI, RST, BL, BUF>,
+    offset: Point,
+}
+
+impl<DI, RST, BL, const BUF: usize> DrawTarget for Translated<'_, DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    type Color = Rgb565;
+    type Error = Error<DI::PinError, DI::BusError>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let offset = self.offset;
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(coord, color)| Pixel(coord + offset, color)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.display.fill_contiguous(
+            &Rectangle::new(area.top_left + self.offset, area.size),
+            colors,
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.fill_solid(
+            &Rectangle::new(area.top_left + self.offset, area.size),
+            color,
+        )
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> OriginDimensions for Translated<'_, DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    fn size(&self) -> Size {
+        self.display.size()
+    }
+}
+
+// Implementing the OriginDimensions trait for the GC9A01A display driver
+impl<DI, RST, BL, const BUF: usize> OriginDimensions for GC9A01A<DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+/// A structure representing a frame buffer.
+///
+/// The frame buffer keeps the bounding box of everything drawn into it, which
+/// [`GC9A01A::flush_framebuffer`] uses to send only the changed area.
+pub struct FrameBuffer<'a> {
+    buffer: &'a mut [u8],
+    width: u32,
+    height: u32,
+    dirty: Option<Rectangle>,
+}
+
+impl<'a> FrameBuffer<'a> {
+    /// Creates a new frame buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A mutable slice representing the pixel data.
+    /// * `width` - The width of the frame buffer.
+    /// * `height` - The height of the frame buffer.
+    pub fn new(buffer: &'a mut [u8], width: u32, height: u32) -> Self {
+        Self {
+            buffer,
+            width,
+            height,
+            dirty: None,
+        }
+    }
+
+    /// Returns the bounding box of everything drawn since the frame buffer was
+    /// created or last marked clean, or `None` if nothing was drawn.
+    pub fn dirty_area(&self) -> Option<Rectangle> {
+        self.dirty
+    }
+
+    /// Forgets the dirty area, as after the frame buffer has been sent.
+    pub fn mark_clean(&mut self) {
+        self.dirty = None;
+    }
+
+    /// Adds an area to the dirty bounding box.
+    fn mark_dirty(&mut self, area: Rectangle) {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return;
+        }
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => union(&dirty, &area),
+            None => area,
+        });
+    }
+
+    /// Returns a reference to the buffer.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the buffer.
+    pub fn get_buffer(&self) -> &[u8] {
+        self.buffer
+    }
+
+    /// Clears the frame buffer with the specified color.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color to clear the buffer with.
+    pub fn clear(&mut self, color: Rgb565) {
+        let raw_color = color.into_storage();
+        for chunk in self.buffer.chunks_exact_mut(2) {
+            chunk[0] = (raw_color >> 8) as u8;
+            chunk[1] = raw_color as u8;
+        }
+        self.mark_dirty(self.bounding_box());
+    }
+
+    /// Copies a region from another buffer into this buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `src_buffer` - The source buffer.
+    /// * `src_x` - The x-coordinate of the top-left corner of the source region.
+    /// * `src_y` - The y-coordinate of the top-left corner of the source region.
+    /// * `src_width` - The width of the source region.
+    /// * `src_height` - The height of the source region.
+    /// * `dest_x` - The x-coordinate of the top-left corner of the destination region.
+    /// * `dest_y` - The y-coordinate of the top-left corner of the destination region.
+    #[allow(clippy::too_many_arguments)]
+    pub fn copy_region(
+        &mut self,
+        src_buffer: &[u8],
+        src_x: u16,
+        src_y: u16,
+        src_width: u32,
+        src_height: u32,
+        dest_x: u16,
+        dest_y: u16,
+    ) {
+        for row in 0..src_height as usize {
+            let src_row_start =
+                (src_y as usize + row) * self.width as usize * 2 + src_x as usize * 2;
+            let src_row_end = src_row_start + src_width as usize * 2;
+
+            let dest_row_start =
+                (dest_y as usize + row) * self.width as usize * 2 + dest_x as usize * 2;
+            let dest_row_end = dest_row_start + src_width as usize * 2;
+
+            self.buffer[dest_row_start..dest_row_end]
+                .copy_from_slice(&src_buffer[src_row_start..src_row_end]);
+        }
+        self.mark_dirty(Rectangle::new(
+            Point::new(dest_x as i32, dest_y as i32),
+            Size::new(src_width, src_height),
+        ));
+    }
+
+    /// Restores regions from a source buffer into the frame buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `src_buffer` - The source buffer.
+    /// * `regions` - An array of regions to restore.
+    pub fn copy_regions(&mut self, src_buffer: &[u8], regions: &[Option<Region>]) {
+        for region in regions.iter().flatten() {
+            self.copy_region(
+                src_buffer,
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                region.x,
+                region.y,
+            );
+        }
+    }
+
+    /// Compares the current frame buffer with another frame buffer and returns an iterator
+    /// of `Pixel` that can be drawn to update the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other frame buffer to compare against.
+    ///
+    /// # Returns
+    ///
+    /// An iterator of `Pixel<Rgb565>`.
+    pub fn diff_with<'b>(
+        &'b self,
+        other: &'b FrameBuffer<'a>,
+    ) -> impl Iterator<Item = Pixel<Rgb565>> + 'b {
+        self.buffer
+            .chunks_exact(2)
+            .enumerate()
+            .filter_map(move |(i, chunk)| {
+                let other_chunk = &other.buffer[i * 2..i * 2 + 2];
+                if chunk != other_chunk {
+                    let x = (i as u32 % self.width) as i32;
+                    let y = (i as u32 / self.width) as i32;
+                    let raw_color = u16::from_be_bytes([chunk[0], chunk[1]]);
+                    let color = Rgb565::from(RawU16::new(raw_color));
+                    Some(Pixel(Point::new(x, y), color))
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+impl<'a> DrawTarget for FrameBuffer<'a> {
+    type Color = Rgb565;
+    type Error = ();
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        // Corners of the area drawn by this call
+        let mut drawn: Option<(Point, Point)> = None;
+
+        for Pixel(coord, color) in pixels {
+            if coord.x >= 0
+                && coord.x < self.width as i32
+                && coord.y >= 0
+                && coord.y < self.height as i32
+            {
+                let index = ((coord.y as u32 * self.width + coord.x as u32) * 2) as usize;
+                let raw_color = color.into_storage();
+                self.buffer[index] = (raw_color >> 8) as u8;
+                self.buffer[index + 1] = raw_color as u8;
+
+                drawn = Some(match drawn {
+                    Some((min, max)) => (min.component_min(coord), max.component_max(coord)),
+                    None => (coord, coord),
+                });
+            }
+        }
+
+        if let Some((min, max)) = drawn {
+            self.mark_dirty(Rectangle::with_corners(min, max));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> OriginDimensions for FrameBuffer<'a> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
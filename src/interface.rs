@@ -0,0 +1,460 @@
+//! Transport between the driver and the display controller.
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::{Operation, SpiBus, SpiDevice};
+
+use crate::Error;
+
+/// Byte-level transport used by the driver to talk to the display controller.
+///
+/// The driver only ever sends commands and data, so implementing this trait is all
+/// that is needed to run the GC9A01A over a wiring other than 4-wire SPI, such as
+/// an 8-bit parallel (8080) bus.
+pub trait DisplayInterface {
+    /// Error type of the control pins.
+    type PinError;
+
+    /// Error type of the data bus.
+    type BusError;
+
+    /// Sends a command byte followed by its parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to write.
+    /// * `params` - Parameters for the command.
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<Self::PinError, Self::BusError>>;
+
+    /// Sends data bytes, such as pixel data following a memory write.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data to write.
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<Self::PinError, Self::BusError>>;
+
+    /// Sends a sequence of commands with their parameters, such as an init table.
+    ///
+    /// The default implementation calls `write_command` for each entry. Interfaces
+    /// that manage chip select can override it to send the whole sequence in one
+    /// transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - Commands to write, each with its parameters.
+    fn write_commands(
+        &mut self,
+        commands: &[(u8, &[u8])],
+    ) -> Result<(), Error<Self::PinError, Self::BusError>> {
+        for (command, params) in commands {
+            self.write_command(*command, params)?;
+        }
+        Ok(())
+    }
+
+    /// Keeps the display selected across the following calls until
+    /// `end_transaction`.
+    ///
+    /// The default implementation does nothing, for interfaces whose chip select is
+    /// managed elsewhere or that have none.
+    fn begin_transaction(&mut self) -> Result<(), Error<Self::PinError, Self::BusError>> {
+        Ok(())
+    }
+
+    /// Ends a transaction started with `begin_transaction`, deselecting the display.
+    ///
+    /// The default implementation does nothing.
+    fn end_transaction(&mut self) -> Result<(), Error<Self::PinError, Self::BusError>> {
+        Ok(())
+    }
+}
+
+/// Display interface that can also read responses back from the controller.
+pub trait ReadInterface: DisplayInterface {
+    /// Sends a command byte and reads its response.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to write.
+    /// * `buffer` - Buffer the response is read into.
+    fn read_command(
+        &mut self,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<Self::PinError, Self::BusError>>;
+}
+
+/// 4-wire SPI interface with data/command and chip select pins.
+pub struct SpiInterface<SPI, DC, CS> {
+    /// SPI interface.
+    spi: SPI,
+
+    /// Data/command pin.
+    dc: DC,
+
+    /// Chip select pin.
+    cs: CS,
+
+    /// Whether CS is held low by an open transaction.
+    held: bool,
+}
+
+impl<SPI, DC, CS> SpiInterface<SPI, DC, CS>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin,
+    CS: OutputPin<Error = DC::Error>,
+{
+    /// Creates a new SPI interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI interface.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    pub fn new(spi: SPI, dc: DC, cs: CS) -> Self {
+        SpiInterface {
+            spi,
+            dc,
+            cs,
+            held: false,
+        }
+    }
+
+    /// Releases the SPI bus and pins.
+    pub fn release(self) -> (SPI, DC, CS) {
+        (self.spi, self.dc, self.cs)
+    }
+
+    /// Runs `transfer` with CS low, then raises CS unless a transaction holds it low.
+    ///
+    /// If the transfer fails, CS is raised even within a transaction, and lowered
+    /// again if one is open, so the controller drops the partial transfer and a
+    /// retry starts on a fresh CS cycle.
+    fn selected<F>(&mut self, transfer: F) -> Result<(), Error<DC::Error, SPI::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error<DC::Error, SPI::Error>>,
+    {
+        self.cs.set_low().map_err(Error::Pin)?;
+        if let Err(error) = transfer(self) {
+            self.cs.set_high().map_err(Error::Pin)?;
+            if self.held {
+                self.cs.set_low().map_err(Error::Pin)?;
+            }
+            return Err(error);
+        }
+        if !self.held {
+            self.cs.set_high().map_err(Error::Pin)?;
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, DC, CS> DisplayInterface for SpiInterface<SPI, DC, CS>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin,
+    CS: OutputPin<Error = DC::Error>,
+{
+    type PinError = DC::Error;
+    type BusError = SPI::Error;
+
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        // The command and its parameters are sent in a single CS-low transaction
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.selected(|interface| {
+            interface.spi.write(&[command]).map_err(Error::Spi)?;
+            if !params.is_empty() {
+                interface.spi.flush().map_err(Error::Spi)?;
+                interface.dc.set_high().map_err(Error::Pin)?;
+                interface.spi.write(params).map_err(Error::Spi)?;
+            }
+            interface.spi.flush().map_err(Error::Spi)
+        })
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.selected(|interface| {
+            interface.spi.write(data).map_err(Error::Spi)?;
+            interface.spi.flush().map_err(Error::Spi)
+        })
+    }
+
+    fn write_commands(
+        &mut self,
+        commands: &[(u8, &[u8])],
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        if commands.is_empty() {
+            return Ok(());
+        }
+
+        // CS stays low for the whole sequence; only DC changes between phases
+        self.selected(|interface| {
+            for (command, params) in commands {
+                interface.dc.set_low().map_err(Error::Pin)?;
+                interface.spi.write(&[*command]).map_err(Error::Spi)?;
+                interface.spi.flush().map_err(Error::Spi)?;
+                if !params.is_empty() {
+                    interface.dc.set_high().map_err(Error::Pin)?;
+                    interface.spi.write(params).map_err(Error::Spi)?;
+                    interface.spi.flush().map_err(Error::Spi)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.cs.set_low().map_err(Error::Pin)?;
+        self.held = true;
+        Ok(())
+    }
+
+    fn end_transaction(&mut self) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.held = false;
+        self.cs.set_high().map_err(Error::Pin)
+    }
+}
+
+impl<SPI, DC, CS> ReadInterface for SpiInterface<SPI, DC, CS>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin,
+    CS: OutputPin<Error = DC::Error>,
+{
+    fn read_command(
+        &mut self,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.selected(|interface| {
+            interface.spi.write(&[command]).map_err(Error::Spi)?;
+            interface.spi.flush().map_err(Error::Spi)?;
+            interface.dc.set_high().map_err(Error::Pin)?;
+            interface.spi.read(buffer).map_err(Error::Spi)?;
+            interface.spi.flush().map_err(Error::Spi)
+        })
+    }
+}
+
+/// SPI interface for a device on a shared bus, with chip select managed externally.
+///
+/// Takes an `embedded-hal` [`SpiDevice`], such as one provided by a bus manager
+/// that owns the chip select pin, so the driver never touches CS itself.
+///
+/// Every data write, including a full frame passed to `show`, is a single
+/// [`SpiDevice::write`], which a DMA-capable HAL can send in one go. A command and
+/// its parameters are two device transactions, because DC has to change between
+/// them and an `embedded-hal` 1.0 transaction cannot drive a GPIO mid-way.
+pub struct SpiDeviceInterface<SPI, DC> {
+    /// SPI device.
+    spi: SPI,
+
+    /// Data/command pin.
+    dc: DC,
+}
+
+impl<SPI, DC> SpiDeviceInterface<SPI, DC>
+where
+    SPI: SpiDevice<u8>,
+    DC: OutputPin,
+{
+    /// Creates a new SPI device interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI device.
+    /// * `dc` - Data/command pin.
+    pub fn new(spi: SPI, dc: DC) -> Self {
+        SpiDeviceInterface { spi, dc }
+    }
+
+    /// Releases the SPI device and data/command pin.
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+}
+
+impl<SPI, DC> DisplayInterface for SpiDeviceInterface<SPI, DC>
+where
+    SPI: SpiDevice<u8>,
+    DC: OutputPin,
+{
+    type PinError = DC::Error;
+    type BusError = SPI::Error;
+
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.spi.write(&[command]).map_err(Error::Spi)?;
+        if !params.is_empty() {
+            self.write_data(params)?;
+        }
+        Ok(())
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<DC::Error, SPI::Error>> {
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.spi.write(data).map_err(Error::Spi)
+    }
+}
+
+impl<SPI, DC> ReadInterface for SpiDeviceInterface<SPI, DC>
+where
+    SPI: SpiDevice<u8>,
+    DC: OutputPin,
+{
+    fn read_command(
+        &mut self,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<DC::Error, SPI::Error>> {
+        // The response must be read in the same transaction, so DC stays low
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.spi
+            .transaction(&mut [Operation::Write(&[command]), Operation::Read(buffer)])
+            .map_err(Error::Spi)
+    }
+}
+
+/// 3-wire SPI interface using 9-bit words, for modules without a DC pin.
+///
+/// The data/command flag is sent as the most significant of the nine bits of each
+/// word. The SPI bus must be configured for 9-bit frames.
+pub struct Spi9BitInterface<SPI, CS> {
+    /// SPI interface.
+    spi: SPI,
+
+    /// Chip select pin.
+    cs: CS,
+
+    /// Whether CS is held low by an open transaction.
+    held: bool,
+}
+
+impl<SPI, CS> Spi9BitInterface<SPI, CS>
+where
+    SPI: SpiBus<u16>,
+    CS: OutputPin,
+{
+    /// Creates a new 9-bit SPI interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI interface, configured for 9-bit frames.
+    /// * `cs` - Chip select pin.
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        Spi9BitInterface {
+            spi,
+            cs,
+            held: false,
+        }
+    }
+
+    /// Releases the SPI bus and chip select pin.
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+
+    /// Sends bytes as 9-bit words with the data/command flag set to `flag`.
+    fn send(&mut self, flag: u16, bytes: &[u8]) -> Result<(), Error<CS::Error, SPI::Error>> {
+        let mut words = [0u16; 32];
+        for chunk in bytes.chunks(words.len()) {
+            for (word, byte) in words.iter_mut().zip(chunk) {
+                *word = flag | *byte as u16;
+            }
+            self.spi.write(&words[..chunk.len()]).map_err(Error::Spi)?;
+        }
+        Ok(())
+    }
+
+    /// Runs `transfer` with CS low, then raises CS unless a transaction holds it low.
+    ///
+    /// As with [`SpiInterface`], a failed transfer always gets a CS edge so a retry
+    /// starts on a fresh CS cycle.
+    fn selected<F>(&mut self, transfer: F) -> Result<(), Error<CS::Error, SPI::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error<CS::Error, SPI::Error>>,
+    {
+        self.cs.set_low().map_err(Error::Pin)?;
+        if let Err(error) = transfer(self) {
+            self.cs.set_high().map_err(Error::Pin)?;
+            if self.held {
+                self.cs.set_low().map_err(Error::Pin)?;
+            }
+            return Err(error);
+        }
+        if !self.held {
+            self.cs.set_high().map_err(Error::Pin)?;
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, CS> DisplayInterface for Spi9BitInterface<SPI, CS>
+where
+    SPI: SpiBus<u16>,
+    CS: OutputPin,
+{
+    type PinError = CS::Error;
+    type BusError = SPI::Error;
+
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<CS::Error, SPI::Error>> {
+        self.selected(|interface| {
+            interface.send(0x000, &[command])?;
+            interface.send(0x100, params)?;
+            interface.spi.flush().map_err(Error::Spi)
+        })
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<CS::Error, SPI::Error>> {
+        self.selected(|interface| {
+            interface.send(0x100, data)?;
+            interface.spi.flush().map_err(Error::Spi)
+        })
+    }
+
+    fn write_commands(
+        &mut self,
+        commands: &[(u8, &[u8])],
+    ) -> Result<(), Error<CS::Error, SPI::Error>> {
+        if commands.is_empty() {
+            return Ok(());
+        }
+
+        self.selected(|interface| {
+            for (command, params) in commands {
+                interface.send(0x000, &[*command])?;
+                interface.send(0x100, params)?;
+            }
+            interface.spi.flush().map_err(Error::Spi)
+        })
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), Error<CS::Error, SPI::Error>> {
+        self.cs.set_low().map_err(Error::Pin)?;
+        self.held = true;
+        Ok(())
+    }
+
+    fn end_transaction(&mut self) -> Result<(), Error<CS::Error, SPI::Error>> {
+        self.held = false;
+        self.cs.set_high().map_err(Error::Pin)
+    }
+}
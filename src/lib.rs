@@ -1,55 +1,242 @@
 #![no_std]
-#![no_main]
 
-use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
-use embedded_hal::blocking::delay::DelayMs;
-use embedded_hal::blocking::spi::Write;
-use embedded_hal::digital::v2::OutputPin;
-use embedded_graphics::pixelcolor::raw::RawU16;
+use core::task::Poll;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::spi::{SpiBus, SpiDevice};
+
+#[cfg(feature = "async")]
+pub mod asynch;
+
+pub mod colors;
+mod console;
+mod interface;
+
+#[cfg(feature = "graphics")]
+mod graphics;
+
+#[cfg(feature = "framebuffer")]
+mod framebuffer;
+
+mod primitives;
+
+pub use colors::Color;
+pub use console::TextConsole;
+#[cfg(feature = "framebuffer")]
+pub use framebuffer::StaticFrameBuffer;
+#[cfg(feature = "graphics")]
+pub use graphics::FrameBuffer;
+pub use interface::{
+    DisplayInterface, ReadInterface, Spi9BitInterface, SpiDeviceInterface, SpiInterface,
+};
 
 /// Enumeration of instructions for the GC9A01A display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Instruction {
-    Nop = 0x00,     // No Operation
-    SwReset = 0x01, // Software Reset
-    RddId = 0x04,   // Read Display Identification Information
-    RddSt = 0x09,   // Read Display Status
-    SlpIn = 0x10,   // Enter Sleep Mode
-    SlpOut = 0x11,  // Sleep Out Mode
-    PtlOn = 0x12,   // Partial Mode ON
-    NorOn = 0x13,   // Normal Display Mode ON
-    InvOff = 0x20,  // Display Inversion OFF
-    InvOn = 0x21,   // Display Inversion ON
-    DispOff = 0x28, // Display OFF
-    DispOn = 0x29,  // Display ON
-    CaSet = 0x2A,   // Column Address Set
-    RaSet = 0x2B,   // Row Address Set
-    RamWr = 0x2C,   // Memory Write
-    RamRd = 0x2E,   // Memory Read
-    PtlAr = 0x30,   // Partial Area
-    ColMod = 0x3A,  // Pixel Format Set
-    MadCtl = 0x36,  // Memory Access Control
-    FrmCtr1 = 0xB1, // Frame Rate Control (In normal mode/Full colors)
-    FrmCtr2 = 0xB2, // Frame Rate Control (In idle mode/8 colors)
-    FrmCtr3 = 0xB3, // Frame Rate Control (In partial mode/full colors)
-    InvCtr = 0xB4,  // Display Inversion Control
-    DisSet5 = 0xB6, // Display Function Control
-    PwCtr1 = 0xC0,  // Power Control 1
-    PwCtr2 = 0xC1,  // Power Control 2
-    PwCtr3 = 0xC2,  // Power Control 3
-    PwCtr4 = 0xC3,  // Power Control 4
-    PwCtr5 = 0xC4,  // Power Control 5
-    VmCtr1 = 0xC5,  // VCOM Control 1
-    RdId1 = 0xDA,   // Read ID1
-    RdId2 = 0xDB,   // Read ID2
-    RdId3 = 0xDC,   // Read ID3
-    RdId4 = 0xDD,   // Read ID4
-    PwCtr6 = 0xFC,  // Power Control 6
-    GmcTrp1 = 0xE0, // Positive Gamma Correction
-    GmcTrn1 = 0xE1, // Negative Gamma Correction
+    Nop = 0x00,       // No Operation
+    SwReset = 0x01,   // Software Reset
+    RddId = 0x04,     // Read Display Identification Information
+    RddSt = 0x09,     // Read Display Status
+    RddColMod = 0x0C, // Read Display Pixel Format
+    SlpIn = 0x10,     // Enter Sleep Mode
+    SlpOut = 0x11,    // Sleep Out Mode
+    PtlOn = 0x12,     // Partial Mode ON
+    NorOn = 0x13,     // Normal Display Mode ON
+    InvOff = 0x20,    // Display Inversion OFF
+    InvOn = 0x21,     // Display Inversion ON
+    DispOff = 0x28,   // Display OFF
+    DispOn = 0x29,    // Display ON
+    CaSet = 0x2A,     // Column Address Set
+    RaSet = 0x2B,     // Row Address Set
+    RamWr = 0x2C,     // Memory Write
+    RamRd = 0x2E,     // Memory Read
+    PtlAr = 0x30,     // Partial Area
+    VScrDef = 0x33,   // Vertical Scrolling Definition
+    TeOff = 0x34,     // Tearing Effect Line OFF
+    TeOn = 0x35,      // Tearing Effect Line ON
+    ColMod = 0x3A,    // Pixel Format Set
+    WrDisBv = 0x51,   // Write Display Brightness
+    WrCtrlD = 0x53,   // Write CTRL Display
+    MadCtl = 0x36,    // Memory Access Control
+    VScSAd = 0x37,    // Vertical Scrolling Start Address
+    IdmOff = 0x38,    // Idle Mode OFF
+    IdmOn = 0x39,     // Idle Mode ON
+    FrmCtr1 = 0xB1,   // Frame Rate Control (In normal mode/Full colors)
+    FrmCtr2 = 0xB2,   // Frame Rate Control (In idle mode/8 colors)
+    FrmCtr3 = 0xB3,   // Frame Rate Control (In partial mode/full colors)
+    InvCtr = 0xB4,    // Display Inversion Control
+    DisSet5 = 0xB6,   // Display Function Control
+    PwCtr1 = 0xC0,    // Power Control 1
+    PwCtr2 = 0xC1,    // Power Control 2
+    PwCtr3 = 0xC2,    // Power Control 3
+    PwCtr4 = 0xC3,    // Power Control 4
+    PwCtr5 = 0xC4,    // Power Control 5
+    VmCtr1 = 0xC5,    // VCOM Control 1
+    VReg2a = 0xC9,    // Vreg2a Voltage Control
+    RdId1 = 0xDA,     // Read ID1
+    RdId2 = 0xDB,     // Read ID2
+    RdId3 = 0xDC,     // Read ID3
+    RdId4 = 0xDD,     // Read ID4
+    PwCtr6 = 0xFC,    // Power Control 6
+    GmcTrp1 = 0xE0,   // Positive Gamma Correction
+    FrmRate = 0xE8,   // Frame Rate
+    GmcTrn1 = 0xE1,   // Negative Gamma Correction
+    Gamma1 = 0xF0,    // Set Gamma 1
+    Gamma2 = 0xF1,    // Set Gamma 2
+    Gamma3 = 0xF2,    // Set Gamma 3
+    Gamma4 = 0xF3,    // Set Gamma 4
+}
+
+/// Vendor gamma table, split over two 6-byte registers per polarity.
+///
+/// This is the table from the controller vendor's reference init sequence, which
+/// is the only one published for the GC9A01A, so there are no other presets to
+/// choose from; calibrated tables are passed to `set_gamma` directly.
+///
+/// These are the values `init` writes to 0xF0-0xF3, the same registers as
+/// `set_gamma`, so `set_gamma(&DEFAULT_GAMMA, &DEFAULT_GAMMA)` restores the init
+/// state and is a starting point for calibration.
+pub const DEFAULT_GAMMA: [u8; 12] = [
+    0x45, 0x09, 0x08, 0x08, 0x26, 0x2A, 0x43, 0x70, 0x72, 0x36, 0x37, 0x6F,
+];
+
+/// Command sequence sent by `init` after the hardware reset.
+///
+/// Shared by the blocking and async drivers. Panels that need different register
+/// values can copy and adjust it, then pass it to `init_with`.
+pub const INIT_SEQUENCE: &[(u8, &[u8])] = &[
+    (0xEF, &[]), // Inter Register Enable 2 (0xEF)
+    (0xEB, &[0x14]),
+    (0xFE, &[]), // Inter Register Enable 1 (0xFE)
+    (0xEF, &[]), // Inter Register Enable 2 (0xEF)
+    (0xEB, &[0x14]),
+    (0x84, &[0x40]),
+    (0x85, &[0xFF]),
+    (0x86, &[0xFF]),
+    (0x87, &[0xFF]),
+    (0x88, &[0x0A]),
+    (0x89, &[0x21]),
+    (0x8A, &[0x00]),
+    (0x8B, &[0x80]),
+    (0x8C, &[0x01]),
+    (0x8D, &[0x01]),
+    (0x8E, &[0xFF]),
+    (0x8F, &[0xFF]),
+    (Instruction::DisSet5 as u8, &[0x00, 0x20]), // Display Function Control (0xB6)
+    (Instruction::MadCtl as u8, &[0x98]),        // Memory Access Control (MADCTL)
+    (Instruction::ColMod as u8, &[0x05]),        // Pixel Format Set (COLMOD)
+    (0x90, &[0x08, 0x08, 0x08, 0x08]),
+    (0xBD, &[0x06]),
+    (0xBC, &[0x00]),
+    (0xFF, &[0x60, 0x01, 0x04]),
+    (Instruction::PwCtr4 as u8, &[0x13]), // Power Control 4 (PWCTR4)
+    (Instruction::PwCtr5 as u8, &[0x13]), // Power Control 5 (PWCTR5)
+    (Instruction::VReg2a as u8, &[0x22]), // Vreg2a Voltage Control (0xC9)
+    (0xBE, &[0x11]),
+    (Instruction::GmcTrn1 as u8, &[0x10, 0x0E]), // Negative Gamma Correction (GMCTRN1)
+    (0xDF, &[0x21, 0x0C, 0x02]),
+    (
+        Instruction::Gamma1 as u8,
+        &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A],
+    ), // SET_GAMMA1 (0xF0)
+    (0xF1, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6F]), // SET_GAMMA2 (0xF1)
+    (0xF2, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A]),
+    (0xF3, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6F]),
+    (0xED, &[0x1B, 0x0B]),
+    (0xAE, &[0x77]),
+    (0xCD, &[0x63]),
+    (
+        0x70,
+        &[0x07, 0x07, 0x04, 0x0E, 0x0F, 0x09, 0x07, 0x08, 0x03],
+    ),
+    (Instruction::FrmRate as u8, &[FrameRate::Default as u8]), // Frame Rate (0xE8)
+    (
+        0x62,
+        &[
+            0x18, 0x0D, 0x71, 0xED, 0x70, 0x70, 0x18, 0x0F, 0x71, 0xEF, 0x70, 0x70,
+        ],
+    ),
+    (
+        0x63,
+        &[
+            0x18, 0x11, 0x71, 0xF1, 0x70, 0x70, 0x18, 0x13, 0x71, 0xF3, 0x70, 0x70,
+        ],
+    ),
+    (0x64, &[0x28, 0x29, 0xF1, 0x01, 0xF1, 0x00, 0x07]),
+    (
+        0x66,
+        &[0x3C, 0x00, 0xCD, 0x67, 0x45, 0x45, 0x10, 0x00, 0x00, 0x00],
+    ),
+    (
+        0x67,
+        &[0x00, 0x3C, 0x00, 0x00, 0x00, 0x01, 0x54, 0x10, 0x32, 0x98],
+    ),
+    (0x74, &[0x10, 0x85, 0x80, 0x00, 0x00, 0x4E, 0x00]),
+    (0x98, &[0x3E, 0x07]),
+    (Instruction::CaSet as u8, &[]),
+    (Instruction::InvOn as u8, &[]),  // Display Inversion ON (INVON)
+    (Instruction::SlpOut as u8, &[]), // Sleep Out Mode (SLPOUT)
+    (Instruction::DispOn as u8, &[]), // Display ON (DISPON)
+];
+
+/// Delays the datasheet requires after commands of an init sequence, in
+/// milliseconds.
+///
+/// `init` and `init_with` wait this long after sending a listed command, before the
+/// next one. Sleep out needs its supply voltages to settle before the panel is
+/// switched on, and display on needs a frame before drawing begins.
+pub const POST_COMMAND_DELAYS: &[(u8, u32)] = &[
+    (Instruction::SlpOut as u8, 120), // Sleep Out Mode (SLPOUT)
+    (Instruction::DispOn as u8, 20),  // Display ON (DISPON)
+];
+
+/// Returns the delay listed in [`POST_COMMAND_DELAYS`] for a command, or 0.
+pub(crate) fn post_command_delay_ms(command: u8) -> u32 {
+    POST_COMMAND_DELAYS
+        .iter()
+        .find(|(listed, _)| *listed == command)
+        .map_or(0, |(_, ms)| *ms)
+}
+
+/// Errors that can occur when communicating with the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<PinE, SpiE> {
+    /// Error reported by the SPI bus.
+    Spi(SpiE),
+
+    /// Error reported by one of the control pins (DC, CS, RST or backlight).
+    Pin(PinE),
+
+    /// Coordinates or region outside the display bounds, or display dimensions that
+    /// do not fit the frame memory.
+    OutOfBounds,
+
+    /// All region slots are already in use.
+    RegionsFull,
+
+    /// Buffer is too short for the area it is drawn to.
+    BufferTooSmall,
+
+    /// Number of pixels does not match the size of the window they are drawn to.
+    WrongPixelCount,
+
+    /// The display did not become ready, or signal a tearing effect, in time.
+    Timeout,
+
+    /// The pixel format read back from the controller differs from the one set.
+    ColorFormatMismatch,
+
+    /// Command not allowed in the current power state, such as `sleep_in` while
+    /// already asleep.
+    InvalidState,
 }
 
 /// Structure to represent a region.
-#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Region {
     pub x: u16,
     pub y: u16,
@@ -58,38 +245,85 @@ pub struct Region {
 }
 
 /// Driver for the GC9A01A display.
-pub struct GC9A01A<SPI, DC, CS, RST>
+///
+/// `DI` is the interface used to talk to the controller, usually a [`SpiInterface`].
+/// The `BL` parameter is the backlight pin, which defaults to `()` for boards
+/// where the backlight is not controlled by the driver. `BUF` is the size in bytes
+/// of the stack buffer used to batch pixel data into SPI transfers.
+pub struct GC9A01A<DI, RST, BL = (), const BUF: usize = DEFAULT_BUFFER_SIZE>
 where
-    SPI: Write<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
 {
-    /// SPI interface.
-    spi: SPI,
-
-    /// Data/command pin.
-    dc: DC,
+    /// Display interface.
+    interface: DI,
 
-    /// Chip select pin.
-    cs: CS,
+    /// Reset pin, or `None` for boards where the reset line is not connected.
+    rst: Option<RST>,
 
-    /// Reset pin.
-    rst: RST,
+    /// Backlight pin.
+    bl: BL,
 
     /// Whether the display is RGB (true) or BGR (false).
     rgb: bool,
 
-    /// Global image offset.
+    /// Global image offset, in the controller's native (portrait) frame.
     dx: u16,
     dy: u16,
     width: u32,
     height: u32,
     regions: [Option<Region>; 10],
+
+    /// Current orientation, re-applied by `init`. `None` until one is set.
+    orientation: Option<Orientation>,
+
+    /// Current pixel format, re-applied by `init`.
+    color_format: ColorFormat,
+
+    /// Byte order of 16-bit pixel data.
+    pixel_order: PixelOrder,
+
+    /// Whether full-screen clears skip the corners outside the round panel.
+    circular_clip: bool,
+
+    /// Horizontal and vertical mirroring, applied on top of the orientation.
+    mirror: (bool, bool),
+
+    /// Whether color inversion is on, applied by `init`.
+    inverted: bool,
+
+    /// Reversed vertical (ML) and horizontal (MH) refresh order.
+    refresh_order: (bool, bool),
+
+    /// Number of times a transfer failing with a bus error is re-issued.
+    retries: u8,
+
+    /// Whether the controller is in sleep mode.
+    sleeping: bool,
+
+    /// Whether SLPIN or SWRESET was sent without the 120ms wait that must pass
+    /// before SLPOUT.
+    sleep_settling: bool,
+
+    /// Whether the display output is on.
+    display_on: bool,
+
+    /// Caller-provided transfer buffer used instead of the stack buffers.
+    dma_buffer: Option<&'static mut [u8]>,
+
+    /// Position the controller writes to next after a `write_pixel`, if nothing
+    /// else has been sent since.
+    pixel_cursor: Option<(u16, u16)>,
+
+    /// Last value written to MADCTL.
+    madctl: u8,
 }
 
 /// Display orientation.
-#[derive(Clone, Copy)]
+///
+/// The values are the row/column exchange and address order bits of MADCTL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Orientation {
     Portrait = 0x00,
     Landscape = 0x60,
@@ -97,734 +331,5328 @@ pub enum Orientation {
     LandscapeSwapped = 0xA0,
 }
 
-impl<SPI, DC, CS, RST> GC9A01A<SPI, DC, CS, RST>
-where
-    SPI: Write<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
-{
-    /// Creates a new driver instance that uses hardware SPI.
-    ///
-    /// # Arguments
-    ///
-    /// * `spi` - SPI interface.
-    /// * `dc` - Data/command pin.
-    /// * `cs` - Chip select pin.
-    /// * `rst` - Reset pin.
-    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
-    /// * `width` - Width of the display.
-    /// * `height` - Height of the display.
-    pub fn new(spi: SPI, dc: DC, cs: CS, rst: RST, rgb: bool, width: u32, height: u32) -> Self {
-        GC9A01A {
-            spi,
-            dc,
-            cs,
-            rst,
-            rgb,
-            dx: 0,
-            dy: 0,
-            width,
-            height,
-            regions: [None; 10],
+/// Tearing effect (TE) output line mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TearingEffect {
+    /// TE output disabled.
+    Off,
+    /// TE pulses during vertical blanking only.
+    VBlank,
+    /// TE pulses during both vertical and horizontal blanking.
+    VBlankHBlank,
+}
+
+/// Pixel format used for color data sent to the display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ColorFormat {
+    /// 16 bits per pixel, sent as two bytes.
+    Rgb565 = 0x05,
+    /// 18 bits per pixel, sent as three bytes with the color in the upper six bits.
+    Rgb666 = 0x06,
+}
+
+/// Byte order of 16-bit pixel data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PixelOrder {
+    /// High byte first, as the GC9A01A expects by default.
+    BigEndian,
+    /// Low byte first, for clones configured for little-endian pixel data.
+    LittleEndian,
+}
+
+/// Power state of the display, as tracked by the driver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PowerState {
+    /// In sleep mode, as after a reset. Drawing returns `Error::InvalidState` until
+    /// the display is woken by `init` or `sleep_out`.
+    Sleeping,
+    /// Awake with the display output off.
+    DisplayOff,
+    /// Awake with the display output on.
+    DisplayOn,
+}
+
+/// Built-in patterns drawn by `self_test` for bring-up and production testing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TestPattern {
+    /// Eight vertical bars: white, yellow, cyan, green, magenta, red, blue and black.
+    /// Swapped red and blue reveal an RGB/BGR mismatch.
+    ColorBars,
+    /// White lines through the center and a ring at the edge of the round panel, on
+    /// black. Reveals offset and orientation errors.
+    Crosshair,
+    /// Red, green and blue ramps from dark to full intensity, one per horizontal
+    /// band. Missing steps reveal stuck data bits.
+    Gradient,
+}
+
+/// Frame rate presets.
+///
+/// The GC9A01A sets its refresh rate through the line period of the frame rate
+/// register (0xE8): longer line periods give lower refresh rates. The rates given
+/// for each preset are approximate, for the nominal internal oscillator, at which
+/// `Default` refreshes at about 60Hz; the oscillator varies between panels, so
+/// measure the rate on the TE line if it matters. `Default` is the value written
+/// by `init`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FrameRate {
+    /// Shortest line period, about 69Hz.
+    Highest = 0x31,
+    /// Shorter line period than the default, about 65Hz.
+    High = 0x32,
+    /// Line period set by `init`, about 60Hz.
+    Default = 0x34,
+    /// Longer line period than the default, about 55Hz.
+    Low = 0x36,
+    /// Longest line period, about 51Hz.
+    Lowest = 0x38,
+}
+
+impl ColorFormat {
+    /// Returns the number of bytes sent per pixel.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            ColorFormat::Rgb565 => 2,
+            ColorFormat::Rgb666 => 3,
         }
     }
 
-    /// Initializes the display.
-    ///
-    /// This function initializes the display by sending a sequence of commands and settings
-    /// to configure the display properly. It includes a hardware reset and various configuration
-    /// commands.
-    ///
-    /// # Arguments
-    ///
-    /// * `delay` - Delay provider.
-    ///
-    /// # Returns
-    ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn init<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
-    where
-        DELAY: DelayMs<u8>,
-    {
-        self.hard_reset(delay)?;
-        self.write_command(0xEF, &[])?; // Inter Register Enable 2 (0xEF)
-        self.write_command(0xEB, &[0x14])?;
-        self.write_command(0xFE, &[])?; // Inter Register Enable 1 (0xFE)
-        self.write_command(0xEF, &[])?; // Inter Register Enable 2 (0xEF)
-        self.write_command(0xEB, &[0x14])?;
-        self.write_command(0x84, &[0x40])?;
-        self.write_command(0x85, &[0xFF])?;
-        self.write_command(0x86, &[0xFF])?;
-        self.write_command(0x87, &[0xFF])?;
-        self.write_command(0x88, &[0x0A])?;
-        self.write_command(0x89, &[0x21])?;
-        self.write_command(0x8A, &[0x00])?;
-        self.write_command(0x8B, &[0x80])?;
-        self.write_command(0x8C, &[0x01])?;
-        self.write_command(0x8D, &[0x01])?;
-        self.write_command(0x8E, &[0xFF])?;
-        self.write_command(0x8F, &[0xFF])?;
-        self.write_command(Instruction::DisSet5 as u8, &[0x00, 0x20])?; // Display Function Control (0xB6)
-        self.write_command(Instruction::MadCtl as u8, &[0x98])?; // Memory Access Control (MADCTL)
-        self.write_command(Instruction::ColMod as u8, &[0x05])?; // Pixel Format Set (COLMOD)
-        self.write_command(0x90, &[0x08, 0x08, 0x08, 0x08])?;
-        self.write_command(0xBD, &[0x06])?;
-        self.write_command(0xBC, &[0x00])?;
-        self.write_command(0xFF, &[0x60, 0x01, 0x04])?;
-        self.write_command(Instruction::PwCtr4 as u8, &[0x13])?; // Power Control 4 (PWCTR4)
-        self.write_command(Instruction::PwCtr5 as u8, &[0x13])?; // Power Control 5 (PWCTR5)
-        self.write_command(0xC9, &[0x22])?;
-        self.write_command(0xBE, &[0x11])?;
-        self.write_command(Instruction::GmcTrn1 as u8, &[0x10, 0x0E])?; // Negative Gamma Correction (GMCTRN1)
-        self.write_command(0xDF, &[0x21, 0x0C, 0x02])?;
-        self.write_command(
-            Instruction::GmcTrp1 as u8,
-            &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A],
-        )?; // Positive Gamma Correction (GMCTRP1)
-        self.write_command(0xF1, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6F])?; // SET_GAMMA2 (0xF1)
-        self.write_command(0xF2, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A])?;
-        self.write_command(0xF3, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6F])?;
-        self.write_command(0xED, &[0x1B, 0x0B])?;
-        self.write_command(0xAE, &[0x77])?;
-        self.write_command(0xCD, &[0x63])?;
-        self.write_command(
-            0x70,
-            &[0x07, 0x07, 0x04, 0x0E, 0x0F, 0x09, 0x07, 0x08, 0x03],
-        )?;
-        self.write_command(Instruction::FrmCtr1 as u8, &[0x34])?; // Frame Rate Control (FRMCTR1)
-        self.write_command(
-            0x62,
-            &[
-                0x18, 0x0D, 0x71, 0xED, 0x70, 0x70, 0x18, 0x0F, 0x71, 0xEF, 0x70, 0x70,
-            ],
-        )?;
-        self.write_command(
-            0x63,
-            &[
-                0x18, 0x11, 0x71, 0xF1, 0x70, 0x70, 0x18, 0x13, 0x71, 0xF3, 0x70, 0x70,
-            ],
-        )?;
-        self.write_command(0x64, &[0x28, 0x29, 0xF1, 0x01, 0xF1, 0x00, 0x07])?;
-        self.write_command(
-            0x66,
-            &[0x3C, 0x00, 0xCD, 0x67, 0x45, 0x45, 0x10, 0x00, 0x00, 0x00],
-        )?;
-        self.write_command(
-            0x67,
-            &[0x00, 0x3C, 0x00, 0x00, 0x00, 0x01, 0x54, 0x10, 0x32, 0x98],
-        )?;
-        self.write_command(0x74, &[0x10, 0x85, 0x80, 0x00, 0x00, 0x4E, 0x00])?;
-        self.write_command(0x98, &[0x3E, 0x07])?;
-        self.write_command(Instruction::CaSet as u8, &[])?;
-        self.write_command(Instruction::InvOn as u8, &[])?; // Display Inversion ON (INVON)
-        self.write_command(Instruction::SlpOut as u8, &[])?; // Sleep Out Mode (SLPOUT)
-        self.write_command(Instruction::DispOn as u8, &[])?; // Display ON (DISPON)
-
-        delay.delay_ms(200);
+    /// Encodes an RGB565 color into `out`, which must hold `bytes_per_pixel` bytes.
+    fn encode(self, color: u16, out: &mut [u8]) {
+        match self {
+            ColorFormat::Rgb565 => out.copy_from_slice(&color.to_be_bytes()),
+            ColorFormat::Rgb666 => {
+                let r = (color >> 11) as u8 & 0x1F;
+                let g = (color >> 5) as u8 & 0x3F;
+                let b = color as u8 & 0x1F;
+                // Widen red and blue to six bits by repeating their top bit
+                out[0] = ((r << 1) | (r >> 4)) << 2;
+                out[1] = g << 2;
+                out[2] = ((b << 1) | (b >> 4)) << 2;
+            }
+        }
+    }
+}
 
-        Ok(())
+/// Default size in bytes of the buffer used to batch pixel data into transfers.
+pub const DEFAULT_BUFFER_SIZE: usize = 32;
+
+/// Largest number of bytes sent per pixel in any color format.
+const MAX_BYTES_PER_PIXEL: usize = 3;
+
+/// Width and height of the controller's frame memory.
+const FRAME_MEMORY_SIZE: u32 = 240;
+
+/// Returns whether a panel of the given size is non-empty and fits the frame memory.
+pub(crate) const fn fits_frame_memory(width: u32, height: u32) -> bool {
+    width > 0 && width <= FRAME_MEMORY_SIZE && height > 0 && height <= FRAME_MEMORY_SIZE
+}
+
+/// Time the reset line is held low by `hard_reset`, in microseconds.
+///
+/// This is the datasheet minimum; `DelayNs` waits at least this long.
+pub const RESET_PULSE_US: u32 = 10;
+
+/// Time `hard_reset` waits after releasing the reset line, in milliseconds.
+pub const RESET_SETTLE_MS: u32 = 120;
+
+/// Interval between polls of the TE pin by `wait_for_tearing_effect`, in
+/// microseconds.
+const TE_POLL_US: u32 = 10;
+
+/// RDDST bit reporting that the booster voltage is on.
+const STATUS_BOOSTER_ON: u32 = 0x8000_0000;
+
+/// MADCTL bit selecting BGR color order.
+const MADCTL_BGR: u8 = 0x08;
+
+/// MADCTL bit reversing the vertical refresh order.
+const MADCTL_ML: u8 = 0x10;
+
+/// MADCTL bit reversing the horizontal refresh order.
+const MADCTL_MH: u8 = 0x04;
+
+/// MADCTL bit exchanging rows and columns.
+const MADCTL_MV: u8 = 0x20;
+
+/// MADCTL bit mirroring the column address order.
+const MADCTL_MX: u8 = 0x40;
+
+/// MADCTL bit mirroring the row address order.
+const MADCTL_MY: u8 = 0x80;
+
+impl Orientation {
+    /// Returns whether rows and columns are exchanged in this orientation.
+    fn is_landscape(self) -> bool {
+        self as u8 & MADCTL_MV != 0
     }
+}
 
-    /// Performs a hard reset of the display.
-    ///
-    /// This function performs a hard reset by toggling the reset pin, ensuring the display
-    /// is in a known state before initialization.
-    ///
-    /// # Arguments
-    ///
-    /// * `delay` - Delay provider.
-    ///
-    /// # Returns
-    ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn hard_reset<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), ()>
-    where
-        DELAY: DelayMs<u8>,
-    {
-        self.rst.set_high().map_err(|_| ())?;
-        delay.delay_ms(10);
-        self.rst.set_low().map_err(|_| ())?;
-        delay.delay_ms(10);
-        self.rst.set_high().map_err(|_| ())?;
-        delay.delay_ms(10);
+/// Placeholder reset pin type for drivers created without a reset pin.
+///
+/// Used by [`GC9A01A::new_without_reset`] and
+/// [`GC9A01A::with_interface_without_reset`]; it is never driven.
+pub struct NoResetPin<E> {
+    _error: core::marker::PhantomData<E>,
+}
+
+impl<E> NoResetPin<E> {
+    fn new() -> Self {
+        NoResetPin {
+            _error: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<E: embedded_hal::digital::Error> embedded_hal::digital::ErrorType for NoResetPin<E> {
+    type Error = E;
+}
 
+impl<E: embedded_hal::digital::Error> OutputPin for NoResetPin<E> {
+    fn set_low(&mut self) -> Result<(), E> {
         Ok(())
     }
 
-    /// Writes a command to the display.
+    fn set_high(&mut self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+/// Known-good configurations for 1.28" round GC9A01A modules.
+///
+/// A preset sets the width, height, offset and color order in one step. The
+/// round modules seen so far, including Waveshare's, all use the whole frame
+/// memory with BGR color order, so there is a single preset. Boards that differ can
+/// be configured with [`GC9A01A::builder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ModulePreset {
+    /// Generic 240x240 round module using the whole frame memory, BGR color order.
+    GenericRound240,
+}
+
+impl ModulePreset {
+    /// Returns the width, height, horizontal and vertical offset, and RGB flag.
+    fn config(self) -> (u32, u32, u16, u16, bool) {
+        match self {
+            ModulePreset::GenericRound240 => (240, 240, 0, 0, false),
+        }
+    }
+}
+
+/// Panel resolutions the GC9A01A can drive, for use with [`Builder::resolution`].
+///
+/// Naming the resolution avoids passing a mismatched width and height. The
+/// controller's frame memory is 240x240, so panels larger than that, such as
+/// 320x240, cannot be driven by it and have no variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Resolution {
+    /// 240x240, the whole frame memory, as on the round 1.28" modules.
+    R240x240,
+}
+
+impl Resolution {
+    /// Returns the width and height in portrait orientation.
+    pub const fn size(self) -> (u32, u32) {
+        match self {
+            Resolution::R240x240 => (240, 240),
+        }
+    }
+}
+
+/// Builder for configuring a [`GC9A01A`] driver.
+///
+/// Created with [`GC9A01A::builder`].
+pub struct Builder<DI, RST> {
+    interface: DI,
+    rst: RST,
+    rgb: bool,
+    width: u32,
+    height: u32,
+    dx: u16,
+    dy: u16,
+    orientation: Option<Orientation>,
+    color_format: ColorFormat,
+    invert: bool,
+}
+
+impl<DI, RST> Builder<DI, RST>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Sets whether the display is RGB (true) or BGR (false).
+    pub fn rgb(mut self, rgb: bool) -> Self {
+        self.rgb = rgb;
+        self
+    }
+
+    /// Sets the width and height of the display.
     ///
-    /// This function sends a command followed by optional parameters to the display.
+    /// Sizes that are 0 or do not fit the 240x240 frame memory are rejected by
+    /// `build`; [`Builder::resolution`] avoids them altogether.
+    pub fn dimensions(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the width and height of the display from a named resolution.
+    pub fn resolution(self, resolution: Resolution) -> Self {
+        let (width, height) = resolution.size();
+        self.dimensions(width, height)
+    }
+
+    /// Sets the orientation applied by `init`.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    /// Sets the global offset of the displayed image, given in portrait orientation.
+    pub fn offset(mut self, dx: u16, dy: u16) -> Self {
+        self.dx = dx;
+        self.dy = dy;
+        self
+    }
+
+    /// Sets whether `init` turns color inversion on, as most round modules need.
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    /// Sets the pixel format written to COLMOD by `init`.
+    pub fn color_format(mut self, format: ColorFormat) -> Self {
+        self.color_format = format;
+        self
+    }
+
+    /// Sets the dimensions, offset and color order of a known module.
+    pub fn preset(self, preset: ModulePreset) -> Self {
+        let (width, height, dx, dy, rgb) = preset.config();
+        self.dimensions(width, height).offset(dx, dy).rgb(rgb)
+    }
+
+    /// Builds the driver.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `command` - Command to write.
-    /// * `params` - Parameters for the command.
+    /// The configured driver, or `Error::OutOfBounds` if the width or height is 0 or
+    /// larger than the 240x240 frame memory.
+    pub fn build(self) -> Result<GC9A01A<DI, RST>, Error<DI::PinError, DI::BusError>> {
+        let mut display =
+            GC9A01A::with_interface(self.interface, self.rst, self.rgb, self.width, self.height)?;
+        display.set_offset(self.dx, self.dy);
+        if let Some(orientation) = self.orientation {
+            if orientation.is_landscape() {
+                core::mem::swap(&mut display.width, &mut display.height);
+            }
+            display.orientation = Some(orientation);
+        }
+        display.color_format = self.color_format;
+        display.inverted = self.invert;
+        Ok(display)
+    }
+}
+
+/// Stepper that sends a full-screen buffer a few rows at a time.
+///
+/// Created with [`GC9A01A::show_chunked`]. Each call to `step` sends the next band
+/// of rows, so the caller can do other work between steps without an async runtime.
+pub struct ShowChunked<'d, 'b, DI, RST, BL, const BUF: usize>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    display: &'d mut GC9A01A<DI, RST, BL, BUF>,
+    buffer: &'b [u8],
+    chunk_rows: u32,
+    next_row: u32,
+}
+
+impl<DI, RST, BL, const BUF: usize> ShowChunked<'_, '_, DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Sends the next band of rows.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn write_command(&mut self, command: u8, params: &[u8]) -> Result<(), ()> {
-        self.cs.set_high().map_err(|_| ())?;
-        self.dc.set_low().map_err(|_| ())?;
-        self.cs.set_low().map_err(|_| ())?;
-        self.spi.write(&[command]).map_err(|_| ())?;
-        if !params.is_empty() {
-            self.start_data()?;
-            self.write_data(params)?;
+    /// `Poll::Pending` while rows remain to be sent, and `Poll::Ready(())` once the
+    /// whole buffer has been sent. Returns `Error::BufferTooSmall` if the buffer does
+    /// not cover the rows of this step.
+    pub fn step(&mut self) -> Result<Poll<()>, Error<DI::PinError, DI::BusError>> {
+        let height = self.display.height;
+        if self.next_row >= height {
+            return Ok(Poll::Ready(()));
+        }
+
+        let rows = self.chunk_rows.min(height - self.next_row);
+        let width = self.display.width;
+        self.display
+            .show_region(self.buffer, 0, self.next_row as u16, width, rows)?;
+        self.next_row += rows;
+
+        if self.next_row >= height {
+            Ok(Poll::Ready(()))
+        } else {
+            Ok(Poll::Pending)
         }
-        self.cs.set_high().map_err(|_| ())?;
-        Ok(())
     }
+}
 
-    /// Starts data transmission.
-    ///
-    /// Sets the data/command pin to indicate data mode for subsequent transmissions.
+/// Guard holding the display selected for a sequence of drawing calls.
+///
+/// Created with [`GC9A01A::start_write`]. It dereferences to the driver, so all
+/// drawing methods can be called through it, and chip select stays asserted
+/// between them. Dropping the guard deselects the display; call `end_write` instead
+/// to see errors from doing so.
+pub struct WriteGuard<'d, DI, RST, BL, const BUF: usize>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    display: &'d mut GC9A01A<DI, RST, BL, BUF>,
+}
+
+impl<DI, RST, BL, const BUF: usize> WriteGuard<'_, DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Ends the write transaction, deselecting the display.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn start_data(&mut self) -> Result<(), ()> {
-        self.dc.set_high().map_err(|_| ())
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn end_write(self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let mut guard = core::mem::ManuallyDrop::new(self);
+        guard.display.interface.end_transaction()
     }
+}
 
-    /// Writes data to the display.
-    ///
-    /// This function writes data to the display through the SPI interface.
+impl<DI, RST, BL, const BUF: usize> core::ops::Deref for WriteGuard<'_, DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    type Target = GC9A01A<DI, RST, BL, BUF>;
+
+    fn deref(&self) -> &Self::Target {
+        self.display
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> core::ops::DerefMut for WriteGuard<'_, DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.display
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> Drop for WriteGuard<'_, DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    fn drop(&mut self) {
+        // Errors cannot be reported from drop; `end_write` returns them
+        let _ = self.display.interface.end_transaction();
+    }
+}
+
+impl<DI, RST> GC9A01A<DI, RST>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Creates a new driver instance that uses the given display interface.
     ///
     /// # Arguments
     ///
-    /// * `data` - Data to write.
+    /// * `interface` - Display interface.
+    /// * `rst` - Reset pin.
+    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
+    /// * `width` - Width of the display.
+    /// * `height` - Height of the display.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn write_data(&mut self, data: &[u8]) -> Result<(), ()> {
-        self.cs.set_high().map_err(|_| ())?;
-        self.dc.set_high().map_err(|_| ())?;
-        self.cs.set_low().map_err(|_| ())?;
-        self.spi.write(data).map_err(|_| ())?;
-        self.cs.set_high().map_err(|_| ())?;
-        Ok(())
+    /// The driver, or `Error::OutOfBounds` if the width or height is 0 or larger
+    /// than the 240x240 frame memory.
+    pub fn with_interface(
+        interface: DI,
+        rst: RST,
+        rgb: bool,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error<DI::PinError, DI::BusError>> {
+        if !fits_frame_memory(width, height) {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(GC9A01A {
+            interface,
+            rst: Some(rst),
+            bl: (),
+            rgb,
+            dx: 0,
+            dy: 0,
+            width,
+            height,
+            regions: [None; 10],
+            orientation: None,
+            color_format: ColorFormat::Rgb565,
+            pixel_order: PixelOrder::BigEndian,
+            circular_clip: false,
+            mirror: (false, false),
+            inverted: true,
+            refresh_order: (false, false),
+            retries: 0,
+            sleeping: true,
+            sleep_settling: false,
+            display_on: false,
+            dma_buffer: None,
+            pixel_cursor: None,
+            madctl: 0,
+        })
     }
+}
 
-    /// Writes a data word to the display.
+impl<DI> GC9A01A<DI, NoResetPin<DI::PinError>>
+where
+    DI: DisplayInterface,
+    DI::PinError: embedded_hal::digital::Error,
+{
+    /// Creates a new driver instance for a display without a reset pin.
     ///
-    /// This function writes a 16-bit word to the display.
+    /// For boards where the display's reset line is tied to the MCU reset or not
+    /// broken out. `init` resets the controller with the SWRESET command instead.
     ///
     /// # Arguments
     ///
-    /// * `value` - Data word to write.
+    /// * `interface` - Display interface.
+    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
+    /// * `width` - Width of the display.
+    /// * `height` - Height of the display.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    fn write_word(&mut self, value: u16) -> Result<(), ()> {
-        self.write_data(&value.to_be_bytes())
+    /// The driver, or `Error::OutOfBounds` if the width or height is 0 or larger
+    /// than the 240x240 frame memory.
+    pub fn with_interface_without_reset(
+        interface: DI,
+        rgb: bool,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error<DI::PinError, DI::BusError>> {
+        let mut display = Self::with_interface(interface, NoResetPin::new(), rgb, width, height)?;
+        display.rst = None;
+        Ok(display)
     }
+}
 
-    /// Sets the orientation of the display.
-    ///
-    /// This function sets the display orientation to one of the predefined modes.
+impl<SPI, DC, CS> GC9A01A<SpiInterface<SPI, DC, CS>, NoResetPin<DC::Error>>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin,
+    CS: OutputPin<Error = DC::Error>,
+{
+    /// Creates a new driver instance that uses hardware SPI, for a display without
+    /// a reset pin.
     ///
     /// # Arguments
     ///
-    /// * `orientation` - Orientation to set.
+    /// * `spi` - SPI interface.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
+    /// * `width` - Width of the display.
+    /// * `height` - Height of the display.
+    ///
+    /// # Returns
+    ///
+    /// The driver, or `Error::OutOfBounds` if the width or height is 0 or larger
+    /// than the 240x240 frame memory.
+    pub fn new_without_reset(
+        spi: SPI,
+        dc: DC,
+        cs: CS,
+        rgb: bool,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error<DC::Error, SPI::Error>> {
+        Self::with_interface_without_reset(SpiInterface::new(spi, dc, cs), rgb, width, height)
+    }
+}
+
+impl<SPI, DC, CS, RST> GC9A01A<SpiInterface<SPI, DC, CS>, RST>
+where
+    SPI: SpiBus<u8>,
+    DC: OutputPin,
+    CS: OutputPin<Error = DC::Error>,
+    RST: OutputPin<Error = DC::Error>,
+{
+    /// Creates a new driver instance that uses hardware SPI.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI interface.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    /// * `rst` - Reset pin.
+    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
+    /// * `width` - Width of the display.
+    /// * `height` - Height of the display.
+    ///
+    /// # Returns
+    ///
+    /// The driver, or `Error::OutOfBounds` if the width or height is 0 or larger
+    /// than the 240x240 frame memory.
+    pub fn new(
+        spi: SPI,
+        dc: DC,
+        cs: CS,
+        rst: RST,
+        rgb: bool,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error<DC::Error, SPI::Error>> {
+        Self::with_interface(SpiInterface::new(spi, dc, cs), rst, rgb, width, height)
+    }
+
+    /// Creates a builder for configuring a new driver instance.
+    ///
+    /// The builder defaults to the common round 240x240 module: BGR color order,
+    /// no offset, and the orientation left as set by `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI interface.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    /// * `rst` - Reset pin.
+    pub fn builder(spi: SPI, dc: DC, cs: CS, rst: RST) -> Builder<SpiInterface<SPI, DC, CS>, RST> {
+        Builder {
+            interface: SpiInterface::new(spi, dc, cs),
+            rst,
+            rgb: false,
+            width: 240,
+            height: 240,
+            dx: 0,
+            dy: 0,
+            orientation: None,
+            color_format: ColorFormat::Rgb565,
+            invert: true,
+        }
+    }
+
+    /// Creates a new instance configured for a known module.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI interface.
+    /// * `dc` - Data/command pin.
+    /// * `cs` - Chip select pin.
+    /// * `rst` - Reset pin.
+    /// * `preset` - Module whose dimensions, offset and color order to use.
+    ///
+    /// # Returns
+    ///
+    /// The driver, or an error from [`Builder::build`].
+    pub fn from_preset(
+        spi: SPI,
+        dc: DC,
+        cs: CS,
+        rst: RST,
+        preset: ModulePreset,
+    ) -> Result<Self, Error<DC::Error, SPI::Error>> {
+        Self::builder(spi, dc, cs, rst).preset(preset).build()
+    }
+}
+
+impl<SPI, DC, RST> GC9A01A<SpiDeviceInterface<SPI, DC>, RST>
+where
+    SPI: SpiDevice<u8>,
+    DC: OutputPin,
+    RST: OutputPin<Error = DC::Error>,
+{
+    /// Creates a new driver instance for a display on a shared SPI bus.
+    ///
+    /// Chip select is handled by the `SpiDevice`, for example one provided by an
+    /// `embedded-hal-bus` bus manager, so the driver never touches it.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI device.
+    /// * `dc` - Data/command pin.
+    /// * `rst` - Reset pin.
+    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
+    /// * `width` - Width of the display.
+    /// * `height` - Height of the display.
+    ///
+    /// # Returns
+    ///
+    /// The driver, or `Error::OutOfBounds` if the width or height is 0 or larger
+    /// than the 240x240 frame memory.
+    pub fn new_spi_device(
+        spi: SPI,
+        dc: DC,
+        rst: RST,
+        rgb: bool,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error<DC::Error, SPI::Error>> {
+        Self::with_interface(SpiDeviceInterface::new(spi, dc), rst, rgb, width, height)
+    }
+}
+
+impl<DI, RST, const BUF: usize> GC9A01A<DI, RST, (), BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Adds a backlight pin to the driver.
+    ///
+    /// Boards that tie the backlight to a fixed supply can skip this, in which case
+    /// the backlight methods are not available.
+    ///
+    /// # Arguments
+    ///
+    /// * `bl` - Backlight pin.
+    pub fn with_backlight<BL>(self, bl: BL) -> GC9A01A<DI, RST, BL, BUF>
+    where
+        BL: OutputPin<Error = DI::PinError>,
+    {
+        GC9A01A {
+            interface: self.interface,
+            rst: self.rst,
+            bl,
+            rgb: self.rgb,
+            dx: self.dx,
+            dy: self.dy,
+            width: self.width,
+            height: self.height,
+            regions: self.regions,
+            orientation: self.orientation,
+            color_format: self.color_format,
+            pixel_order: self.pixel_order,
+            circular_clip: self.circular_clip,
+            mirror: self.mirror,
+            inverted: self.inverted,
+            refresh_order: self.refresh_order,
+            retries: self.retries,
+            sleeping: self.sleeping,
+            sleep_settling: self.sleep_settling,
+            display_on: self.display_on,
+            dma_buffer: self.dma_buffer,
+            pixel_cursor: self.pixel_cursor,
+            madctl: self.madctl,
+        }
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> GC9A01A<DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Changes the size of the buffer used to batch pixel data into SPI transfers.
+    ///
+    /// Larger buffers mean fewer, longer SPI transfers at the cost of stack space.
+    /// The size is given in bytes and must hold at least one 3-byte pixel, which is
+    /// checked at compile time. Pixels are never split across two transfers, so a
+    /// size that is a multiple of the pixel size avoids unused bytes at the end.
+    pub fn with_buffer_size<const N: usize>(self) -> GC9A01A<DI, RST, BL, N> {
+        const {
+            assert!(
+                N >= MAX_BYTES_PER_PIXEL,
+                "the transfer buffer must hold at least one pixel"
+            )
+        };
+
+        GC9A01A {
+            interface: self.interface,
+            rst: self.rst,
+            bl: self.bl,
+            rgb: self.rgb,
+            dx: self.dx,
+            dy: self.dy,
+            width: self.width,
+            height: self.height,
+            regions: self.regions,
+            orientation: self.orientation,
+            color_format: self.color_format,
+            pixel_order: self.pixel_order,
+            circular_clip: self.circular_clip,
+            mirror: self.mirror,
+            inverted: self.inverted,
+            refresh_order: self.refresh_order,
+            retries: self.retries,
+            sleeping: self.sleeping,
+            sleep_settling: self.sleep_settling,
+            display_on: self.display_on,
+            dma_buffer: self.dma_buffer,
+            pixel_cursor: self.pixel_cursor,
+            madctl: self.madctl,
+        }
+    }
+
+    /// Sets how often a transfer that fails with a bus error is re-issued.
+    ///
+    /// This makes long transfers, such as full-frame pushes over a ribbon cable,
+    /// robust against transient bus noise. Each retry repeats the whole command or
+    /// data transfer: a failed chunk of pixel data is re-sent as a whole, not from
+    /// where it failed. The built-in SPI interfaces raise CS when a transfer fails,
+    /// even inside `start_write`, so the controller drops the partial transfer and
+    /// the retry starts on a fresh CS cycle. Pin errors are never retried. The
+    /// default of 0 returns the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `retries` - Number of extra attempts per transfer.
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Makes the buffered write paths send pixel data from a caller-provided buffer.
+    ///
+    /// On MCUs where DMA only works from certain RAM regions, placing this buffer
+    /// there lets pixel data go out over DMA. It replaces both the `BUF`-sized
+    /// transfer buffer and the chunk used for solid fills, so it also sets the size
+    /// of each pixel data transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Transfer buffer, such as a `static` in DMA-capable memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than one 3-byte pixel.
+    pub fn with_dma_buffer(mut self, buffer: &'static mut [u8]) -> Self {
+        assert!(
+            buffer.len() >= MAX_BYTES_PER_PIXEL,
+            "the transfer buffer must hold at least one pixel"
+        );
+        self.dma_buffer = Some(buffer);
+        self
+    }
+
+    /// Consumes the driver and returns the display interface and pins.
+    ///
+    /// For the SPI interface, [`SpiInterface::release`] returns the SPI bus and the
+    /// DC and CS pins in turn:
+    ///
+    /// ```ignore
+    /// let (interface, rst, _) = display.release();
+    /// let (spi, dc, cs) = interface.release();
+    /// ```
+    ///
+    /// The reset pin is `None` for drivers created without one.
+    pub fn release(self) -> (DI, Option<RST>, BL) {
+        (self.interface, self.rst, self.bl)
+    }
+
+    /// Initializes the display.
+    ///
+    /// This function initializes the display by sending a sequence of commands and settings
+    /// to configure the display properly. It includes a hardware reset and various configuration
+    /// commands.
+    /// Commands listed in [`POST_COMMAND_DELAYS`] are followed by their required delay
+    /// before the next command is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn init<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.run_init(delay, INIT_SEQUENCE, 0)
+    }
+
+    /// Initializes the display with a custom command sequence.
+    ///
+    /// Works like `init`, but sends `commands` instead of [`INIT_SEQUENCE`] after the
+    /// hardware reset. The stored orientation and pixel format are applied afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    /// * `commands` - Commands and their parameters, sent in order.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn init_with<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        commands: &[(u8, &[u8])],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.run_init(delay, commands, 0)
+    }
+
+    /// Initializes the display, pausing between the commands of the init sequence.
+    ///
+    /// Some panels fail to initialize intermittently, typically on a cold boot, when
+    /// the commands are sent back to back. A delay of about 1ms between commands
+    /// usually gives them time to settle.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    /// * `per_command_ms` - Delay after each command of the sequence, in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn init_with_delays<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        per_command_ms: u32,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.run_init(delay, INIT_SEQUENCE, per_command_ms)
+    }
+
+    /// Re-initializes a display that is already running, such as after a brownout
+    /// has corrupted its registers.
+    ///
+    /// Forgets the state cached from the controller (MADCTL, sleep and display power,
+    /// and the `write_pixel` cursor) and runs `init` again. Settings made through the
+    /// driver, such as the orientation, mirroring, pixel format and inversion, are
+    /// kept and re-applied, so drawing can continue as before once it returns. The
+    /// frame memory is not restored and must be redrawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn set_orientation(&mut self, orientation: &Orientation) -> Result<(), ()> {
-        if self.rgb {
-            self.write_command(Instruction::MadCtl as u8, &[*orientation as u8])?;
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn reinit<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.pixel_cursor = None;
+        self.madctl = 0;
+        self.sleeping = true;
+        self.display_on = false;
+        self.init(delay)
+    }
+
+    /// Resets the display, sends `commands` with an optional delay after each, and
+    /// re-applies the stored orientation and pixel format.
+    fn run_init<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        commands: &[(u8, &[u8])],
+        per_command_ms: u32,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        if self.rst.is_some() {
+            self.hard_reset(delay)?;
+        } else {
+            self.soft_reset(delay)?;
+        }
+        if per_command_ms > 0 {
+            for (command, params) in commands {
+                match self.init_param(*command, params) {
+                    Some(param) => self.write_command(*command, &[param])?,
+                    None => self.write_command(*command, params)?,
+                }
+                delay.delay_ms(per_command_ms.max(post_command_delay_ms(*command)));
+            }
         } else {
-            self.write_command(Instruction::MadCtl as u8, &[*orientation as u8 | 0x08])?;
+            // Send the table in batches, breaking to adjust MADCTL and COLMOD and to
+            // wait after commands that need a delay
+            let mut rest = commands;
+            while let Some(index) = rest.iter().position(|(command, params)| {
+                self.init_param(*command, params).is_some() || post_command_delay_ms(*command) > 0
+            }) {
+                let (command, params) = rest[index];
+                self.retry(|interface| interface.write_commands(&rest[..index]))?;
+                for (command, params) in &rest[..index] {
+                    self.track_command(*command, params);
+                }
+                match self.init_param(command, params) {
+                    Some(param) => self.write_command(command, &[param])?,
+                    None => self.write_command(command, params)?,
+                }
+                let ms = post_command_delay_ms(command);
+                if ms > 0 {
+                    delay.delay_ms(ms);
+                }
+                rest = &rest[index + 1..];
+            }
+            self.retry(|interface| interface.write_commands(rest))?;
+            for (command, params) in rest {
+                self.track_command(*command, params);
+            }
+        }
+
+        if let Some(orientation) = self.orientation {
+            self.set_orientation(&orientation)?;
+        }
+        // The panel powers up with inversion off
+        let table_inverted = commands
+            .iter()
+            .rev()
+            .find_map(|(command, _)| match *command {
+                c if c == Instruction::InvOn as u8 => Some(true),
+                c if c == Instruction::InvOff as u8 => Some(false),
+                _ => None,
+            })
+            .unwrap_or(false);
+        if table_inverted != self.inverted {
+            if self.inverted {
+                self.invert_on()?;
+            } else {
+                self.invert_off()?;
+            }
         }
+        let sets_format = commands
+            .iter()
+            .any(|(command, _)| *command == Instruction::ColMod as u8);
+        if !sets_format && self.color_format != ColorFormat::Rgb565 {
+            self.set_color_format(self.color_format)?;
+        }
+
         Ok(())
     }
 
-    /// Sets the global offset of the displayed image.
+    /// Returns the parameter to send instead of the table's for an init table entry:
+    /// MADCTL with the color order, mirroring and refresh order applied on top of the
+    /// table's value, and COLMOD with the current pixel format. Returns `None` for all
+    /// other entries.
+    fn init_param(&self, command: u8, params: &[u8]) -> Option<u8> {
+        match params {
+            [value] if command == Instruction::MadCtl as u8 => Some(self.madctl_value(*value)),
+            [_] if command == Instruction::ColMod as u8 => Some(self.color_format as u8),
+            _ => None,
+        }
+    }
+
+    /// Performs a hard reset of the display.
+    ///
+    /// This function performs a hard reset by toggling the reset pin, ensuring the display
+    /// is in a known state before initialization. The reset line is held low for
+    /// `RESET_PULSE_US`, the 10µs minimum, and the controller is then given
+    /// `RESET_SETTLE_MS` to finish resetting. Does nothing for drivers created
+    /// without a reset pin.
     ///
     /// # Arguments
     ///
-    /// * `dx` - Horizontal offset.
-    /// * `dy` - Vertical offset.
-    pub fn set_offset(&mut self, dx: u16, dy: u16) {
-        self.dx = dx;
-        self.dy = dy;
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn hard_reset<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.hard_reset_with_timing(delay, RESET_PULSE_US, RESET_SETTLE_MS)
     }
 
-    /// Sets the address window for the display.
+    /// Performs a hard reset of the display with custom timing.
     ///
-    /// This function sets the address window for subsequent drawing commands.
+    /// The reset pin is driven high, then low for `low_us`, then high again, after
+    /// which the function waits `settle_ms` before returning. Does nothing for
+    /// drivers created without a reset pin.
     ///
     /// # Arguments
     ///
-    /// * `start_x` - Start x-coordinate.
-    /// * `start_y` - Start y-coordinate.
-    /// * `end_x` - End x-coordinate.
-    /// * `end_y` - End y-coordinate.
+    /// * `delay` - Delay provider.
+    /// * `low_us` - Time the reset line is held low, and high before that, in
+    ///   microseconds.
+    /// * `settle_ms` - Time to wait after releasing the reset line, in milliseconds.
     ///
     /// # Returns
     ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn set_address_window(
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn hard_reset_with_timing<DELAY>(
         &mut self,
-        start_x: u16,
-        start_y: u16,
-        end_x: u16,
-        end_y: u16,
-    ) -> Result<(), ()> {
-        self.write_command(Instruction::CaSet as u8, &[])?;
-        self.start_data()?;
-        self.write_word(start_x + self.dx)?;
-        self.write_word(end_x + self.dx)?;
-        self.write_command(Instruction::RaSet as u8, &[])?;
-        self.start_data()?;
-        self.write_word(start_y + self.dy)?;
-        self.write_word(end_y + self.dy)
+        delay: &mut DELAY,
+        low_us: u32,
+        settle_ms: u32,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        let Some(rst) = self.rst.as_mut() else {
+            return Ok(());
+        };
+
+        rst.set_high().map_err(Error::Pin)?;
+        delay.delay_us(low_us);
+        rst.set_low().map_err(Error::Pin)?;
+        delay.delay_us(low_us);
+        rst.set_high().map_err(Error::Pin)?;
+        delay.delay_ms(settle_ms);
+
+        self.pixel_cursor = None;
+        self.madctl = 0;
+        self.sleeping = true;
+        self.sleep_settling = false;
+        self.display_on = false;
+        Ok(())
+    }
+
+    /// Performs a software reset of the display.
+    ///
+    /// Resets the controller with the SWRESET command and waits the required 120ms
+    /// before returning. `init` uses this instead of a hard reset for drivers created
+    /// without a reset pin.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn soft_reset<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        self.write_command(Instruction::SwReset as u8, &[])?;
+        delay.delay_ms(120);
+        self.sleep_settling = false;
+
+        Ok(())
+    }
+
+    /// Puts the display into sleep mode.
+    ///
+    /// The panel stops scanning and draws minimal current until woken with `sleep_out`.
+    /// The datasheet requires 120ms before `sleep_out` may be issued again, so this
+    /// function waits that long before returning.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::InvalidState` without sending anything if the display is already asleep.
+    pub fn sleep_in<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        if self.sleeping {
+            return Err(Error::InvalidState);
+        }
+        self.write_command(Instruction::SlpIn as u8, &[])?;
+        delay.delay_ms(120);
+        self.sleep_settling = false;
+        Ok(())
+    }
+
+    /// Wakes the display from sleep mode.
+    ///
+    /// Must not be issued within 120ms of SLPIN or SWRESET. `sleep_in` and
+    /// `soft_reset` wait that long themselves; if either command was sent without the
+    /// wait, such as with `send_command`, this function makes it up before sending
+    /// SLPOUT. The datasheet requires a further 120ms for the supply voltages to
+    /// settle, so this function waits that long before returning.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::InvalidState` without sending anything if the display is not asleep.
+    pub fn sleep_out<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        if !self.sleeping {
+            return Err(Error::InvalidState);
+        }
+        if self.sleep_settling {
+            delay.delay_ms(120);
+        }
+        self.write_command(Instruction::SlpOut as u8, &[])?;
+        delay.delay_ms(120);
+        Ok(())
+    }
+
+    /// Returns the power state of the display.
+    ///
+    /// The state is tracked from the commands sent by the driver, including those
+    /// in the init table and ones sent with `send_command`. Before `init` the
+    /// display is assumed to be asleep, as after power-on.
+    pub fn power_state(&self) -> PowerState {
+        if self.sleeping {
+            PowerState::Sleeping
+        } else if self.display_on {
+            PowerState::DisplayOn
+        } else {
+            PowerState::DisplayOff
+        }
+    }
+
+    /// Returns whether the display is in sleep mode.
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// Turns the display output on.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Nothing is sent if the
+    /// output is already on, as it is after `init`.
+    pub fn display_on(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if self.display_on {
+            return Ok(());
+        }
+        self.write_command(Instruction::DispOn as u8, &[])
+    }
+
+    /// Turns the display output off.
+    ///
+    /// The frame memory is kept, so drawing can continue while the output is off.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Nothing is sent if the
+    /// output is already off.
+    pub fn display_off(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if !self.display_on {
+            return Ok(());
+        }
+        self.write_command(Instruction::DispOff as u8, &[])
+    }
+
+    /// Turns color inversion on.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn invert_on(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::InvOn as u8, &[])?;
+        self.inverted = true;
+        Ok(())
+    }
+
+    /// Turns color inversion off.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn invert_off(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::InvOff as u8, &[])?;
+        self.inverted = false;
+        Ok(())
+    }
+
+    /// Returns whether color inversion is on.
+    ///
+    /// Inversion is on by default, as most round modules need it to show black as
+    /// black; `init` applies this setting.
+    pub fn inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Configures the tearing effect (TE) output line.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Tearing effect mode to set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_tearing_effect(
+        &mut self,
+        mode: TearingEffect,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        match mode {
+            TearingEffect::Off => self.write_command(Instruction::TeOff as u8, &[]),
+            TearingEffect::VBlank => self.write_command(Instruction::TeOn as u8, &[0x00]),
+            TearingEffect::VBlankHBlank => self.write_command(Instruction::TeOn as u8, &[0x01]),
+        }
+    }
+
+    /// Sets the refresh rate of the panel.
+    ///
+    /// Changing the refresh rate can move visible tearing, or reduce power at
+    /// lower rates.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Frame rate preset to set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_frame_rate(
+        &mut self,
+        rate: FrameRate,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::FrmRate as u8, &[rate as u8])
+    }
+
+    /// Sends a no-op command.
+    ///
+    /// Useful as a keep-alive, or to end a memory write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn nop(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::Nop as u8, &[])
+    }
+
+    /// Sets the display brightness through the controller.
+    ///
+    /// Enables the controller's brightness control and writes the level, for dimming
+    /// modules whose backlight is not connected to a pin. This only has an effect on
+    /// panels whose backlight is driven by the controller's brightness output.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Brightness level, from 0 (darkest) to 255 (brightest).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        // Brightness control block (BCTRL) and backlight control (BL) on
+        self.write_command(Instruction::WrCtrlD as u8, &[0x24])?;
+        self.write_command(Instruction::WrDisBv as u8, &[level])
+    }
+
+    /// Sets the voltage regulator levels that determine contrast.
+    ///
+    /// `init` writes `0x13` to both Vreg1a and Vreg1b and `0x22` to Vreg2a; start
+    /// from those and adjust in small steps, since values far from them can make
+    /// the image washed out or unreadable.
+    ///
+    /// # Arguments
+    ///
+    /// * `vreg1a` - Vreg1a voltage level (0xC3).
+    /// * `vreg1b` - Vreg1b voltage level (0xC4).
+    /// * `vreg2a` - Vreg2a voltage level (0xC9).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_power_control(
+        &mut self,
+        vreg1a: u8,
+        vreg1b: u8,
+        vreg2a: u8,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::PwCtr4 as u8, &[vreg1a])?;
+        self.write_command(Instruction::PwCtr5 as u8, &[vreg1b])?;
+        self.write_command(Instruction::VReg2a as u8, &[vreg2a])
+    }
+
+    /// Waits for the start of the next blanking period on the TE line.
+    ///
+    /// Blocks until a rising edge is seen on the TE pin, so that a following `show`
+    /// starts writing while the panel is not scanning. Requires the tearing effect
+    /// line to be enabled with `set_tearing_effect`. The pin is polled every 10µs,
+    /// so the wait ends even if TE never toggles, such as when it is disabled,
+    /// disconnected or the panel is asleep.
+    ///
+    /// # Arguments
+    ///
+    /// * `te` - Tearing effect input pin.
+    /// * `delay` - Delay provider, used to wait between polls.
+    /// * `timeout_ms` - Maximum time to wait, in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::Timeout` if
+    /// no rising edge is seen within `timeout_ms`.
+    pub fn wait_for_tearing_effect<TE, DELAY>(
+        &mut self,
+        te: &mut TE,
+        delay: &mut DELAY,
+        timeout_ms: u32,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        TE: InputPin<Error = DI::PinError>,
+        DELAY: DelayNs,
+    {
+        let mut polls = timeout_ms.saturating_mul(1000 / TE_POLL_US);
+        // Wait out the current pulse, then for the next one to start
+        for level in [true, false] {
+            while te.is_high().map_err(Error::Pin)? == level {
+                if polls == 0 {
+                    return Err(Error::Timeout);
+                }
+                polls -= 1;
+                delay.delay_us(TE_POLL_US);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the gamma correction tables.
+    ///
+    /// Each table is written to a pair of the controller's gamma registers
+    /// (0xF0/0xF1 for positive, 0xF2/0xF3 for negative), six bytes each.
+    /// [`DEFAULT_GAMMA`] holds the values used by `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `positive` - Positive polarity gamma table.
+    /// * `negative` - Negative polarity gamma table.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_gamma(
+        &mut self,
+        positive: &[u8; 12],
+        negative: &[u8; 12],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::Gamma1 as u8, &positive[..6])?;
+        self.write_command(Instruction::Gamma2 as u8, &positive[6..])?;
+        self.write_command(Instruction::Gamma3 as u8, &negative[..6])?;
+        self.write_command(Instruction::Gamma4 as u8, &negative[6..])
+    }
+
+    /// Sets the pixel format used for color data.
+    ///
+    /// In [`ColorFormat::Rgb666`] mode the pixel writing methods send three bytes per
+    /// pixel instead of two. Raw buffers passed to `show`, `show_region` and
+    /// `draw_image` are sent as-is and must already be in the selected format.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - Pixel format to set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_color_format(
+        &mut self,
+        format: ColorFormat,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::ColMod as u8, &[format as u8])?;
+        self.color_format = format;
+        Ok(())
+    }
+
+    /// Returns the current pixel format.
+    pub fn color_format(&self) -> ColorFormat {
+        self.color_format
+    }
+
+    /// Sets the byte order in which 16-bit pixels are sent.
+    ///
+    /// Some GC9A01A clones expect the low byte of each RGB565 pixel first. With
+    /// [`PixelOrder::LittleEndian`] the pixel writing methods swap the bytes of each
+    /// pixel as they encode it, so colors need not be swapped beforehand. Command
+    /// parameters such as window coordinates are always sent high byte first, raw
+    /// buffers passed to `show` or `draw_image` are sent as-is, and 18-bit pixels
+    /// are not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - Byte order of pixel data.
+    pub fn set_pixel_order(&mut self, order: PixelOrder) {
+        self.pixel_order = order;
+    }
+
+    /// Returns the byte order of 16-bit pixel data.
+    pub fn pixel_order(&self) -> PixelOrder {
+        self.pixel_order
+    }
+
+    /// Limits the display output to a band of rows.
+    ///
+    /// Rows outside the partial area are not driven, which saves power for content
+    /// such as an always-on clock. The line arguments are clamped to the display height.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_line` - First row of the partial area.
+    /// * `end_line` - Last row of the partial area.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn partial_mode_on(
+        &mut self,
+        start_line: u16,
+        end_line: u16,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let last_line = self.height.saturating_sub(1) as u16;
+        let mut params = [0; 4];
+        params[..2].copy_from_slice(&start_line.min(last_line).to_be_bytes());
+        params[2..].copy_from_slice(&end_line.min(last_line).to_be_bytes());
+        self.write_command(Instruction::PtlAr as u8, &params)?;
+        self.write_command(Instruction::PtlOn as u8, &[])
+    }
+
+    /// Enters idle mode.
+    ///
+    /// In idle mode the panel only shows 8 colors: each channel is reduced to its
+    /// most significant bit, so colors snap to black, white, red, green, blue, cyan,
+    /// magenta or yellow. Power consumption drops considerably, which suits simple
+    /// always-on content such as a standby clock.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn idle_mode_on(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::IdmOn as u8, &[])
+    }
+
+    /// Leaves idle mode, returning to full color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn idle_mode_off(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::IdmOff as u8, &[])
+    }
+
+    /// Returns to normal display mode, driving the whole panel.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn normal_mode_on(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::NorOn as u8, &[])
+    }
+
+    /// Defines the vertical scrolling area.
+    ///
+    /// The display is split into a fixed area at the top, a scrolling area and a fixed
+    /// area at the bottom, given in rows of frame memory. The three heights should add
+    /// up to the height of the frame memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `top_fixed` - Height of the fixed area at the top.
+    /// * `scroll_height` - Height of the scrolling area.
+    /// * `bottom_fixed` - Height of the fixed area at the bottom.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_scroll_area(
+        &mut self,
+        top_fixed: u16,
+        scroll_height: u16,
+        bottom_fixed: u16,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let mut params = [0; 6];
+        params[..2].copy_from_slice(&top_fixed.to_be_bytes());
+        params[2..4].copy_from_slice(&scroll_height.to_be_bytes());
+        params[4..].copy_from_slice(&bottom_fixed.to_be_bytes());
+        self.write_command(Instruction::VScrDef as u8, &params)
+    }
+
+    /// Sets the frame memory row shown at the top of the scrolling area.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - Frame memory row to start the scrolling area at.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_scroll_start(&mut self, line: u16) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::VScSAd as u8, &line.to_be_bytes())
+    }
+
+    /// Sends a raw command and its parameters to the display.
+    ///
+    /// This is an escape hatch for registers the driver does not otherwise expose,
+    /// such as the vendor registers of the init sequence. Commands that change the orientation,
+    /// pixel format or dimensions bypass the driver's own state.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to write.
+    /// * `params` - Parameters for the command.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn send_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(command, params)
+    }
+
+    /// Starts a write transaction that keeps the display selected.
+    ///
+    /// Chip select is asserted once and stays asserted for every call made through
+    /// the returned guard, instead of being toggled around each command and data
+    /// transfer. It is released by `WriteGuard::end_write` or when the guard is
+    /// dropped. Interfaces without a chip select pin under the driver's control,
+    /// such as `SpiDeviceInterface`, are not affected.
+    ///
+    /// # Returns
+    ///
+    /// The guard, or an error if asserting chip select failed.
+    #[allow(clippy::type_complexity)]
+    pub fn start_write(
+        &mut self,
+    ) -> Result<WriteGuard<'_, DI, RST, BL, BUF>, Error<DI::PinError, DI::BusError>> {
+        self.interface.begin_transaction()?;
+        Ok(WriteGuard { display: self })
+    }
+
+    /// Writes a command to the display.
+    ///
+    /// This function sends a command followed by optional parameters to the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command to write.
+    /// * `params` - Parameters for the command.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_command(
+        &mut self,
+        command: u8,
+        params: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        // Every drawing method writes the frame memory through RAMWR
+        if command == Instruction::RamWr as u8 && self.sleeping {
+            return Err(Error::InvalidState);
+        }
+        self.retry(|interface| interface.write_command(command, params))?;
+        self.track_command(command, params);
+        Ok(())
+    }
+
+    /// Updates the tracked power state and MADCTL value after a command has been sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Command that was sent.
+    /// * `params` - Parameters sent with the command.
+    fn track_command(&mut self, command: u8, params: &[u8]) {
+        match command {
+            c if c == Instruction::SwReset as u8 => {
+                self.sleeping = true;
+                self.sleep_settling = true;
+                self.display_on = false;
+                self.madctl = 0;
+            }
+            c if c == Instruction::MadCtl as u8 => {
+                if let [value] = params {
+                    self.madctl = *value;
+                }
+            }
+            c if c == Instruction::SlpIn as u8 => {
+                self.sleeping = true;
+                self.sleep_settling = true;
+            }
+            c if c == Instruction::SlpOut as u8 => {
+                self.sleeping = false;
+                self.sleep_settling = false;
+            }
+            c if c == Instruction::DispOn as u8 => self.display_on = true,
+            c if c == Instruction::DispOff as u8 => self.display_on = false,
+            _ => {}
+        }
+    }
+
+    /// Writes data to the display.
+    ///
+    /// This function writes data to the display through the display interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data to write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.retry(|interface| interface.write_data(data))
+    }
+
+    /// Runs a transfer on the display interface, re-issuing it on bus errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `transfer` - Transfer to run, called up to `retries + 1` times.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` of the last attempt.
+    fn retry<F>(&mut self, mut transfer: F) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        F: FnMut(&mut DI) -> Result<(), Error<DI::PinError, DI::BusError>>,
+    {
+        // Anything sent moves the controller away from where `write_pixel` left it
+        self.pixel_cursor = None;
+        let mut attempts = self.retries;
+        loop {
+            match transfer(&mut self.interface) {
+                Err(Error::Spi(_)) if attempts > 0 => attempts -= 1,
+                result => return result,
+            }
+        }
+    }
+
+    /// Writes a sequence of colors to the display in the current pixel format.
+    ///
+    /// The colors are packed into the transfer buffer so that they are sent in
+    /// batches rather than one SPI transfer per pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Pixel colors.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_colors_buffered<C: Color, P: IntoIterator<Item = C>>(
+        &mut self,
+        colors: P,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_colors_counted(colors)
+            .map(|_| ())
+            .map_err(|(_, error)| error)
+    }
+
+    /// Streams colors like `write_colors_buffered`, counting the pixels sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Colors to send.
+    ///
+    /// # Returns
+    ///
+    /// The number of pixels sent, or the number sent before the transfer that
+    /// failed together with its error.
+    #[allow(clippy::type_complexity)]
+    fn write_colors_counted<C: Color, P: IntoIterator<Item = C>>(
+        &mut self,
+        colors: P,
+    ) -> Result<usize, (usize, Error<DI::PinError, DI::BusError>)> {
+        self.with_transfer_buffer(|display, buffer| {
+            let bytes_per_pixel = display.color_format.bytes_per_pixel();
+            let mut index = 0;
+            let mut sent = 0;
+            for color in colors {
+                if index + bytes_per_pixel > buffer.len() {
+                    display
+                        .write_data(&buffer[0..index])
+                        .map_err(|error| (sent, error))?;
+                    sent += index / bytes_per_pixel;
+                    index = 0;
+                }
+                display.encode_pixel(color, &mut buffer[index..index + bytes_per_pixel]);
+                index += bytes_per_pixel;
+            }
+            if index > 0 {
+                display
+                    .write_data(&buffer[0..index])
+                    .map_err(|error| (sent, error))?;
+                sent += index / bytes_per_pixel;
+            }
+            Ok(sent)
+        })
+    }
+
+    /// Runs `f` with the buffer used to batch pixel data into transfers.
+    ///
+    /// This is the buffer set with `with_dma_buffer` if there is one, and a
+    /// `BUF`-sized stack buffer otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Function given the driver and the transfer buffer.
+    ///
+    /// # Returns
+    ///
+    /// The result of `f`.
+    fn with_transfer_buffer<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self, &mut [u8]) -> R,
+    {
+        match self.dma_buffer.take() {
+            Some(buffer) => {
+                let result = f(self, buffer);
+                self.dma_buffer = Some(buffer);
+                result
+            }
+            None => f(self, &mut [0; BUF]),
+        }
+    }
+
+    /// Writes one color to the display `count` times in the current pixel format.
+    ///
+    /// The color is encoded once into a chunk of pixels, which is then sent
+    /// repeatedly, so solid fills skip the per-pixel conversion of
+    /// `write_colors_buffered`. The chunk is the buffer set with `with_dma_buffer`
+    /// if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - Pixel color.
+    /// * `count` - Number of pixels to write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn write_color_repeated<C: Color>(
+        &mut self,
+        color: C,
+        count: usize,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        const CHUNK_SIZE: usize = 512;
+
+        match self.dma_buffer.take() {
+            Some(buffer) => {
+                let result = self.send_color_chunk(buffer, color, count);
+                self.dma_buffer = Some(buffer);
+                result
+            }
+            None => {
+                let mut chunk = [0; CHUNK_SIZE * MAX_BYTES_PER_PIXEL];
+                let chunk = &mut chunk[..CHUNK_SIZE * self.color_format.bytes_per_pixel()];
+                self.send_color_chunk(chunk, color, count)
+            }
+        }
+    }
+
+    /// Fills `chunk` with a color and sends it until `count` pixels are written.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - Buffer to fill with the encoded color.
+    /// * `color` - Pixel color.
+    /// * `count` - Number of pixels to write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    fn send_color_chunk<C: Color>(
+        &mut self,
+        chunk: &mut [u8],
+        color: C,
+        count: usize,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let bytes_per_pixel = self.color_format.bytes_per_pixel();
+        let chunk_pixels = count.min(chunk.len() / bytes_per_pixel);
+        let chunk = &mut chunk[..chunk_pixels * bytes_per_pixel];
+
+        // Fill the chunk with the color data
+        for pixel in chunk.chunks_exact_mut(bytes_per_pixel) {
+            self.encode_pixel(color, pixel);
+        }
+
+        // Write data in chunks
+        let mut remaining = count;
+        while remaining > 0 {
+            let pixels = remaining.min(chunk_pixels);
+            self.write_data(&chunk[..pixels * bytes_per_pixel])?;
+            remaining -= pixels;
+        }
+        Ok(())
+    }
+
+    /// Encodes a color into `out` in the current pixel format and byte order.
+    fn encode_pixel<C: Color>(&self, color: C, out: &mut [u8]) {
+        color.encode(self.color_format, out);
+        if self.color_format == ColorFormat::Rgb565 && self.pixel_order == PixelOrder::LittleEndian
+        {
+            out.swap(0, 1);
+        }
+    }
+
+    /// Sets the orientation of the display.
+    ///
+    /// This function sets the display orientation to one of the predefined modes.
+    /// When switching between portrait and landscape, the stored width and height
+    /// are swapped to match. The offset follows the panel, so it keeps pointing at
+    /// the same edge of the frame memory in every orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `orientation` - Orientation to set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_orientation(
+        &mut self,
+        orientation: &Orientation,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let madctl = self.madctl_value(*orientation as u8);
+        self.write_command(Instruction::MadCtl as u8, &[madctl])?;
+
+        let was_landscape = self.orientation.is_some_and(Orientation::is_landscape);
+        if orientation.is_landscape() != was_landscape {
+            core::mem::swap(&mut self.width, &mut self.height);
+        }
+        self.orientation = Some(*orientation);
+
+        Ok(())
+    }
+
+    /// Returns the current orientation, or `None` if none has been set.
+    pub fn orientation(&self) -> Option<Orientation> {
+        self.orientation
+    }
+
+    /// Composes a MADCTL value from the rotation bits of an orientation or of the init
+    /// table, and the color order, mirroring and refresh order settings.
+    fn madctl_value(&self, rotation: u8) -> u8 {
+        let mut value = rotation & !MADCTL_BGR;
+        if !self.rgb {
+            value |= MADCTL_BGR;
+        }
+        if self.mirror.0 {
+            value ^= MADCTL_MX;
+        }
+        if self.mirror.1 {
+            value ^= MADCTL_MY;
+        }
+        if self.refresh_order.0 {
+            value |= MADCTL_ML;
+        }
+        if self.refresh_order.1 {
+            value |= MADCTL_MH;
+        }
+        value
+    }
+
+    /// Sets the order in which the panel is refreshed from frame memory.
+    ///
+    /// Reversing the vertical (ML) or horizontal (MH) refresh order changes the
+    /// direction the image is scanned out, such as to match a panel mounted upside
+    /// down, without mirroring its content. Only the ML and MH bits of MADCTL are
+    /// changed, so the rotation last written, including that of the `init` sequence,
+    /// is kept. The setting is kept across later `set_orientation` calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `reverse_vertical` - Whether to refresh from the bottom line up.
+    /// * `reverse_horizontal` - Whether to refresh each line from right to left.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_refresh_order(
+        &mut self,
+        reverse_vertical: bool,
+        reverse_horizontal: bool,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.refresh_order = (reverse_vertical, reverse_horizontal);
+        let mut madctl = self.madctl & !(MADCTL_ML | MADCTL_MH);
+        if reverse_vertical {
+            madctl |= MADCTL_ML;
+        }
+        if reverse_horizontal {
+            madctl |= MADCTL_MH;
+        }
+        self.write_command(Instruction::MadCtl as u8, &[madctl])
+    }
+
+    /// Sets the color order of the panel at runtime.
+    ///
+    /// Rewrites MADCTL with only the color order bit changed, so the rotation,
+    /// mirroring and refresh order last written, including those of the `init`
+    /// sequence, are kept. The color order is also applied by later `init` and
+    /// `set_orientation` calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `rgb` - Whether the display is RGB (true) or BGR (false).
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_color_order(&mut self, rgb: bool) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.rgb = rgb;
+        let madctl = if rgb {
+            self.madctl & !MADCTL_BGR
+        } else {
+            self.madctl | MADCTL_BGR
+        };
+        self.write_command(Instruction::MadCtl as u8, &[madctl])
+    }
+
+    /// Mirrors the image without changing the rotation.
+    ///
+    /// Horizontal mirroring reverses the column address order (MX) and vertical
+    /// mirroring the row address order (MY), relative to the rotation last written,
+    /// including that of the `init` sequence. Only the MX and MY bits of MADCTL are
+    /// changed. The mirroring is kept across later `set_orientation` calls and
+    /// re-applied by `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `horizontal` - Whether to mirror horizontally.
+    /// * `vertical` - Whether to mirror vertically.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_mirror(
+        &mut self,
+        horizontal: bool,
+        vertical: bool,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let mut madctl = self.madctl;
+        if horizontal != self.mirror.0 {
+            madctl ^= MADCTL_MX;
+        }
+        if vertical != self.mirror.1 {
+            madctl ^= MADCTL_MY;
+        }
+        self.mirror = (horizontal, vertical);
+        self.write_command(Instruction::MadCtl as u8, &[madctl])
+    }
+
+    /// Writes a raw value to the memory access control register (MADCTL).
+    ///
+    /// An escape hatch for panels with unusual requirements and for debugging
+    /// rotation and color issues. The orientation, mirroring, color order and
+    /// refresh order are decoded from the value, so the higher-level setters and
+    /// `init` continue from it.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - MADCTL value to write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_madctl(&mut self, value: u8) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::MadCtl as u8, &[value])?;
+
+        // Prefer an orientation without mirroring where one matches the bits exactly
+        let bits = value & (MADCTL_MY | MADCTL_MX | MADCTL_MV);
+        let orientation = [
+            Orientation::Portrait,
+            Orientation::Landscape,
+            Orientation::PortraitSwapped,
+            Orientation::LandscapeSwapped,
+        ]
+        .into_iter()
+        .find(|orientation| *orientation as u8 == bits)
+        .unwrap_or(if bits & MADCTL_MV != 0 {
+            Orientation::Landscape
+        } else {
+            Orientation::Portrait
+        });
+        let mirrored = bits ^ orientation as u8;
+
+        let was_landscape = self.orientation.is_some_and(Orientation::is_landscape);
+        if orientation.is_landscape() != was_landscape {
+            core::mem::swap(&mut self.width, &mut self.height);
+        }
+        self.orientation = Some(orientation);
+        self.mirror = (mirrored & MADCTL_MX != 0, mirrored & MADCTL_MY != 0);
+        self.rgb = value & MADCTL_BGR == 0;
+        self.refresh_order = (value & MADCTL_ML != 0, value & MADCTL_MH != 0);
+        Ok(())
+    }
+
+    /// Returns the last value written to MADCTL, by any method.
+    pub fn madctl(&self) -> u8 {
+        self.madctl
+    }
+
+    /// Returns the width and height of the display in the current orientation.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Returns the width of the display in the current orientation.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the display in the current orientation.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the number of bytes in a full frame of pixel data in the current
+    /// pixel format, such as the buffer passed to `show`.
+    pub fn bytes_per_frame(&self) -> usize {
+        (self.width * self.height) as usize * self.color_format.bytes_per_pixel()
+    }
+
+    /// Sets the global offset of the displayed image.
+    ///
+    /// The offset is the position of the visible area within the controller's frame
+    /// memory, given in the current orientation. It is converted when the orientation
+    /// changes, so it does not need to be set again after rotating.
+    ///
+    /// # Arguments
+    ///
+    /// * `dx` - Horizontal offset.
+    /// * `dy` - Vertical offset.
+    pub fn set_offset(&mut self, dx: u16, dy: u16) {
+        let (exchanged, mirror_x, mirror_y) = self.address_mapping();
+        let (x, y) = if exchanged { (dy, dx) } else { (dx, dy) };
+        self.dx = self.flip_offset(x, mirror_x, false);
+        self.dy = self.flip_offset(y, mirror_y, true);
+        self.pixel_cursor = None;
+    }
+
+    /// Returns the global offset in the current orientation, as passed to
+    /// `set_offset`.
+    pub fn offset(&self) -> (u16, u16) {
+        let (exchanged, mirror_x, mirror_y) = self.address_mapping();
+        let x = self.flip_offset(self.dx, mirror_x, false);
+        let y = self.flip_offset(self.dy, mirror_y, true);
+        if exchanged {
+            (y, x)
+        } else {
+            (x, y)
+        }
+    }
+
+    /// Returns whether rows and columns are exchanged, and whether the native
+    /// column and row address orders are mirrored, in the current orientation.
+    fn address_mapping(&self) -> (bool, bool, bool) {
+        match self.orientation {
+            Some(orientation) => {
+                let madctl = self.madctl_value(orientation as u8);
+                (
+                    orientation.is_landscape(),
+                    madctl & MADCTL_MX != 0,
+                    madctl & MADCTL_MY != 0,
+                )
+            }
+            None => (false, self.mirror.0, self.mirror.1),
+        }
+    }
+
+    /// Measures an offset along a native axis from the opposite edge of the frame
+    /// memory when that axis is mirrored.
+    fn flip_offset(&self, offset: u16, mirrored: bool, rows: bool) -> u16 {
+        if !mirrored {
+            return offset;
+        }
+        // Extent of the panel along the native axis
+        let landscape = self.orientation.is_some_and(Orientation::is_landscape);
+        let extent = if rows != landscape {
+            self.height
+        } else {
+            self.width
+        };
+        FRAME_MEMORY_SIZE
+            .saturating_sub(extent)
+            .saturating_sub(offset as u32) as u16
+    }
+
+    /// Sets the address window for the display.
+    ///
+    /// This function sets the address window for subsequent drawing commands.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the window does not lie within the display or its end lies before its start,
+    /// or if the offset moves it past the end of the controller's frame memory.
+    pub fn set_address_window(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if end_x < start_x
+            || end_y < start_y
+            || end_x as u32 >= self.width
+            || end_y as u32 >= self.height
+        {
+            return Err(Error::OutOfBounds);
+        }
+
+        let (dx, dy) = self.offset();
+        if end_x as u32 + dx as u32 >= FRAME_MEMORY_SIZE
+            || end_y as u32 + dy as u32 >= FRAME_MEMORY_SIZE
+        {
+            return Err(Error::OutOfBounds);
+        }
+
+        let mut columns = [0; 4];
+        columns[..2].copy_from_slice(&(start_x + dx).to_be_bytes());
+        columns[2..].copy_from_slice(&(end_x + dx).to_be_bytes());
+        self.write_command(Instruction::CaSet as u8, &columns)?;
+
+        let mut rows = [0; 4];
+        rows[..2].copy_from_slice(&(start_y + dy).to_be_bytes());
+        rows[2..].copy_from_slice(&(end_y + dy).to_be_bytes());
+        self.write_command(Instruction::RaSet as u8, &rows)
+    }
+
+    /// Restores the address window to the whole display.
+    ///
+    /// Drawing methods leave the window at the area they drew, so `write_pixels` and
+    /// `write_raw_pixels` would otherwise only fill that area. The display offset is
+    /// applied as with `set_address_window`.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn reset_address_window(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.set_address_window(0, 0, self.width as u16 - 1, self.height as u16 - 1)
+    }
+
+    /// Clears the screen by filling it with a single color.
+    ///
+    /// This function sets the entire display to the specified color. The color is
+    /// converted once into a 512-pixel chunk that is sent repeatedly, which avoids
+    /// per-pixel work while keeping the stack use small.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color to fill the screen with.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn clear_screen<C: Color>(
+        &mut self,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if self.circular_clip {
+            return self.clear_circle(color);
+        }
+
+        // Set the address window to cover the entire screen
+        self.set_address_window(0, 0, self.width as u16 - 1, self.height as u16 - 1)?;
+        self.write_command(Instruction::RamWr as u8, &[])?;
+        self.write_color_repeated(color, (self.width * self.height) as usize)
+    }
+
+    /// Fills the whole display with colors computed from pixel coordinates.
+    ///
+    /// `f` is called once per pixel, row by row, and the colors are streamed through
+    /// the transfer buffer, so generated content such as gradients needs no frame
+    /// buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Function returning the color of the pixel at `(x, y)`.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn fill_with<C, F>(&mut self, mut f: F) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        C: Color,
+        F: FnMut(u16, u16) -> C,
+    {
+        let width = self.width as u16;
+        let height = self.height as u16;
+
+        self.set_address_window(0, 0, width - 1, height - 1)?;
+        self.write_pixels(
+            (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| f(x, y)),
+        )
+    }
+
+    /// Draws a built-in test pattern over the whole display.
+    ///
+    /// Gives every project the same bring-up check of the panel and its wiring,
+    /// drawn with the regular fill and pixel methods.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Pattern to draw.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn self_test(
+        &mut self,
+        pattern: TestPattern,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let (width, height) = (self.width, self.height);
+        match pattern {
+            TestPattern::ColorBars => {
+                const BARS: [u16; 8] = [
+                    colors::WHITE,
+                    colors::YELLOW,
+                    colors::CYAN,
+                    colors::GREEN,
+                    colors::MAGENTA,
+                    colors::RED,
+                    colors::BLUE,
+                    colors::BLACK,
+                ];
+                for (index, color) in BARS.iter().enumerate() {
+                    // The last bar also takes the pixels left over by the division
+                    let x = index as u32 * width / BARS.len() as u32;
+                    let end = (index as u32 + 1) * width / BARS.len() as u32;
+                    self.fill_rect(x as u16, 0, end - x, height, *color)?;
+                }
+                Ok(())
+            }
+            TestPattern::Crosshair => {
+                self.fill_rect(0, 0, width, height, colors::BLACK)?;
+                self.fill_rect(0, (height / 2) as u16, width, 1, colors::WHITE)?;
+                self.fill_rect((width / 2) as u16, 0, 1, height, colors::WHITE)?;
+                let radius = (width.min(height) / 2) as u16;
+                self.draw_ring(
+                    (width / 2) as u16,
+                    (height / 2) as u16,
+                    radius.saturating_sub(2),
+                    radius,
+                    colors::WHITE,
+                )
+            }
+            TestPattern::Gradient => self.fill_with(|x, y| {
+                let level = (x as u32 * 256 / width) as u8;
+                match y as u32 * 3 / height {
+                    0 => colors::rgb565(level, 0, 0),
+                    1 => colors::rgb565(0, level, 0),
+                    _ => colors::rgb565(0, 0, level),
+                }
+            }),
+        }
+    }
+
+    /// Sets whether full-screen clears only write the visible circle of the round panel.
+    ///
+    /// The frame memory is square, so about a fifth of it lies in the corners outside
+    /// the inscribed circle. With clipping enabled, `clear_screen` and `clear` send
+    /// each row only across its visible span, leaving the corners untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to clip full-screen clears to the circle.
+    pub fn set_circular_clip(&mut self, enabled: bool) {
+        self.circular_clip = enabled;
+    }
+
+    /// Returns the first and last column of `row` inside the inscribed circle,
+    /// or `None` if the row has no visible pixels.
+    fn circle_span(&self, row: u32) -> Option<(u16, u16)> {
+        // Distances are in half pixels, so that pixel centers lie on whole numbers
+        let diameter = self.width.min(self.height);
+        let dy = (2 * row + 1).abs_diff(self.height);
+        let half_span = (diameter * diameter).saturating_sub(dy * dy).isqrt();
+
+        let start = self.width.saturating_sub(half_span + 1).div_ceil(2);
+        let end = ((self.width + half_span - 1) / 2).min(self.width - 1);
+        (start <= end).then_some((start as u16, end as u16))
+    }
+
+    /// Fills the inscribed circle of the display with a single color, row by row.
+    fn clear_circle<C: Color>(
+        &mut self,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        for row in 0..self.height {
+            if let Some((start, end)) = self.circle_span(row) {
+                self.set_pixels(
+                    start,
+                    row as u16,
+                    end,
+                    row as u16,
+                    core::iter::repeat_n(color, (end - start + 1) as usize),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets a pixel color at the given coordinates.
+    ///
+    /// This function sets the color of a single pixel at the specified coordinates.
+    /// The address window runs to the end of the row, so when the next call targets
+    /// the pixel to the right and nothing else has been sent in between, only the
+    /// color is sent, without reprogramming the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate.
+    /// * `y` - Y-coordinate.
+    /// * `color` - Color of the pixel.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the pixel lies outside the display.
+    pub fn write_pixel<C: Color>(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let mut pixel = [0; MAX_BYTES_PER_PIXEL];
+        let pixel = &mut pixel[..self.color_format.bytes_per_pixel()];
+        self.encode_pixel(color, pixel);
+
+        if self.pixel_cursor != Some((x, y)) {
+            self.set_address_window(x, y, self.width as u16 - 1, y)?;
+            self.write_command(Instruction::RamWr as u8, &[])?;
+        }
+        self.write_data(pixel)?;
+
+        // The controller wraps to the window start after the last column
+        if (x as u32 + 1) < self.width {
+            self.pixel_cursor = Some((x + 1, y));
+        }
+        Ok(())
+    }
+
+    /// Starts streaming pixels from the given position.
+    ///
+    /// The address window is set once, from `(x, y)` to the bottom-right corner of
+    /// the display. Each following `push_pixel` writes one pixel and advances to the
+    /// right, wrapping to the start column of the next row at the right edge. This
+    /// avoids re-sending the address window for pixels that are drawn in sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the first pixel.
+    /// * `y` - Y-coordinate of the first pixel.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the position lies outside the display.
+    pub fn begin_pixels(
+        &mut self,
+        x: u16,
+        y: u16,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.set_address_window(x, y, self.width as u16 - 1, self.height as u16 - 1)?;
+        self.write_command(Instruction::RamWr as u8, &[])
+    }
+
+    /// Writes the next pixel of a stream started with `begin_pixels`.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - Color of the pixel.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn push_pixel<C: Color>(
+        &mut self,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let mut pixel = [0; MAX_BYTES_PER_PIXEL];
+        let pixel = &mut pixel[..self.color_format.bytes_per_pixel()];
+        self.encode_pixel(color, pixel);
+        self.write_data(pixel)
+    }
+
+    /// Ends a pixel stream started with `begin_pixels`.
+    ///
+    /// Sends a no-op command, which terminates the memory write.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn end_pixels(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.nop()
+    }
+
+    /// Writes pixel colors into the current address window.
+    ///
+    /// This function issues a memory write and streams the colors into the window
+    /// most recently set. Every method that draws to part of the display, such as
+    /// `write_pixel`, `set_pixels`, `fill_rect` or `show_region`, leaves the window
+    /// at the area it drew; call `set_address_window` or `reset_address_window`
+    /// first to draw elsewhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Pixel colors.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn write_pixels<C: Color, P: IntoIterator<Item = C>>(
+        &mut self,
+        colors: P,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::RamWr as u8, &[])?;
+        self.write_colors_buffered(colors)
+    }
+
+    /// Writes pixel colors into the current address window like `write_pixels`, and
+    /// returns how many were sent.
+    ///
+    /// Useful when the colors come from a source that may run short, such as a
+    /// network stream, to check that the whole window was filled.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Pixel colors.
+    ///
+    /// # Returns
+    ///
+    /// The number of pixels sent. On failure, the error comes with the number of
+    /// pixels sent in the transfers that completed before it.
+    #[allow(clippy::type_complexity)]
+    pub fn write_pixels_counted<C: Color, P: IntoIterator<Item = C>>(
+        &mut self,
+        colors: P,
+    ) -> Result<usize, (usize, Error<DI::PinError, DI::BusError>)> {
+        self.write_command(Instruction::RamWr as u8, &[])
+            .map_err(|error| (0, error))?;
+        self.write_colors_counted(colors)
+    }
+
+    /// Streams more pixel colors into a memory write that is still open.
+    ///
+    /// Continues where the previous `write_pixels`, `set_pixels` or
+    /// `continue_pixels` call stopped, without re-sending the address window or the
+    /// memory write command, so a large region can be streamed in chunks such as one
+    /// row at a time. Any command sent in between ends the memory write, after which
+    /// the colors are ignored by the controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Pixel colors.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn continue_pixels<C: Color, P: IntoIterator<Item = C>>(
+        &mut self,
+        colors: P,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_colors_buffered(colors)
+    }
+
+    /// Writes pre-encoded pixel data into the current address window.
+    ///
+    /// Like `write_pixels`, but takes bytes already in the display's pixel format,
+    /// such as an image stored in flash, and sends them without conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Pixel data, big-endian RGB565 unless another format is set.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn write_raw_pixels(
+        &mut self,
+        data: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.write_command(Instruction::RamWr as u8, &[])?;
+        self.write_data(data)
+    }
+
+    /// Sets the colors of the pixels in a rectangular window.
+    ///
+    /// This is meant for windows of differing colors; use `fill_window` to fill a
+    /// window with a single color, which avoids converting the same color per pixel.
+    /// The colors are written row by row, starting at the top-left corner of the window.
+    /// Their number is not checked: missing colors leave the rest of the window
+    /// unchanged, and extra colors wrap around to the top-left corner. Use
+    /// `set_pixels_checked` to get an error instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    /// * `colors` - Pixel colors.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_pixels<C: Color, P: IntoIterator<Item = C>>(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+        colors: P,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.set_address_window(start_x, start_y, end_x, end_y)?;
+        self.write_pixels(colors)
+    }
+
+    /// Sets the colors of pixels from rows of colors, such as the rows of a sprite.
+    ///
+    /// Each row is written into its own one-row window starting at `x`, so rows
+    /// shorter than `width` leave the rest of their line unchanged and longer rows
+    /// are cut at `width`. Rows are clipped at the right edge of the display, and rows
+    /// below the bottom edge are not sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the left edge.
+    /// * `y` - Y-coordinate of the first row.
+    /// * `width` - Largest number of pixels drawn per row.
+    /// * `rows` - Rows of pixel colors, from top to bottom.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_rows<C, R, P>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u32,
+        rows: R,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        C: Color,
+        R: IntoIterator<Item = P>,
+        P: IntoIterator<Item = C>,
+    {
+        if x as u32 >= self.width || width == 0 {
+            return Ok(());
+        }
+        let visible = width.min(self.width - x as u32);
+        let end_x = (x as u32 + visible - 1) as u16;
+
+        for (row_y, row) in (y as u32..self.height).zip(rows) {
+            self.set_address_window(x, row_y as u16, end_x, row_y as u16)?;
+            self.write_pixels(row.into_iter().take(visible as usize))?;
+        }
+        Ok(())
+    }
+
+    /// Fills a rectangular window with a single color.
+    ///
+    /// The solid-fill counterpart to `set_pixels`: the color is converted once and
+    /// sent from a pre-filled chunk, as in `clear_screen`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    /// * `color` - Fill color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the window does not lie within the display.
+    pub fn fill_window<C: Color>(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.set_address_window(start_x, start_y, end_x, end_y)?;
+        self.write_command(Instruction::RamWr as u8, &[])?;
+        let count = (end_x - start_x + 1) as usize * (end_y - start_y + 1) as usize;
+        self.write_color_repeated(color, count)
+    }
+
+    /// Sets the colors of the pixels in a rectangular window, checking that exactly
+    /// one color is given per pixel.
+    ///
+    /// At most as many colors as the window holds are sent, so extra colors never
+    /// wrap around and overwrite the start of the window. If there are too few, the
+    /// rest of the window keeps its previous content.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    /// * `colors` - Pixel colors.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::WrongPixelCount` if `colors` yields more or fewer colors than the
+    /// window holds.
+    pub fn set_pixels_checked<C: Color, P: IntoIterator<Item = C>>(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+        colors: P,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.set_address_window(start_x, start_y, end_x, end_y)?;
+
+        let expected = (end_x - start_x + 1) as usize * (end_y - start_y + 1) as usize;
+        let mut colors = colors.into_iter();
+        let mut written = 0;
+        self.write_pixels(colors.by_ref().take(expected).inspect(|_| written += 1))?;
+
+        if written < expected || colors.next().is_some() {
+            return Err(Error::WrongPixelCount);
+        }
+        Ok(())
+    }
+
+    /// Sets the pixels of a rectangular window from pre-encoded bytes.
+    ///
+    /// The byte-slice counterpart to `set_pixels`: the bytes are sent verbatim after
+    /// the memory write, so data that is already big-endian RGB565, such as image
+    /// rows received over a network, needs no conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    /// * `data` - Pixel data in the current pixel format, row by row.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::WrongPixelCount` without sending anything unless `data` holds exactly
+    /// one pixel for each pixel of the window.
+    pub fn set_pixels_bytes(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+        data: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if end_x >= start_x && end_y >= start_y {
+            let expected = (end_x - start_x + 1) as usize
+                * (end_y - start_y + 1) as usize
+                * self.color_format.bytes_per_pixel();
+            if data.len() != expected {
+                return Err(Error::WrongPixelCount);
+            }
+        }
+
+        self.set_address_window(start_x, start_y, end_x, end_y)?;
+        self.write_raw_pixels(data)
+    }
+
+    /// Sets the colors of the pixels in a rectangular window from 24-bit colors.
+    ///
+    /// Each color is reduced to RGB565 by dropping the low bits of each channel
+    /// before it is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_x` - Start x-coordinate.
+    /// * `start_y` - Start y-coordinate.
+    /// * `end_x` - End x-coordinate.
+    /// * `end_y` - End y-coordinate.
+    /// * `colors` - Pixel colors as `(red, green, blue)`, row by row.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn set_pixels_rgb888<P: IntoIterator<Item = (u8, u8, u8)>>(
+        &mut self,
+        start_x: u16,
+        start_y: u16,
+        end_x: u16,
+        end_y: u16,
+        colors: P,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.set_pixels(
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+            colors.into_iter().map(|(r, g, b)| colors::rgb565(r, g, b)),
+        )
+    }
+
+    /// Draws an image from a slice of RGB565 data.
+    ///
+    /// This function draws an image from a slice of pixel data in RGB565 format.
+    /// The image must cover the whole display.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_data` - Image data to draw.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::WrongPixelCount` without sending anything unless `image_data` holds
+    /// exactly one pixel for each pixel of the display, in the current pixel format.
+    pub fn draw_image(
+        &mut self,
+        image_data: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.draw_image_at(0, 0, self.width, self.height, image_data)
+    }
+
+    /// Draws an image from a slice of RGB565 data at the given position.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the top-left corner of the image.
+    /// * `y` - Y-coordinate of the top-left corner of the image.
+    /// * `img_width` - Width of the image.
+    /// * `img_height` - Height of the image.
+    /// * `image_data` - Image data to draw, row by row.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. An empty image sends nothing.
+    /// Returns `Error::OutOfBounds` if the image does not fit on the display, or
+    /// `Error::WrongPixelCount` unless `image_data` holds exactly one pixel for each
+    /// pixel of the image, in the current pixel format.
+    pub fn draw_image_at(
+        &mut self,
+        x: u16,
+        y: u16,
+        img_width: u32,
+        img_height: u32,
+        image_data: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if img_width == 0 || img_height == 0 {
+            return Ok(());
+        }
+        if x as u32 + img_width > self.width || y as u32 + img_height > self.height {
+            return Err(Error::OutOfBounds);
+        }
+        let expected = (img_width * img_height) as usize * self.color_format.bytes_per_pixel();
+        if image_data.len() != expected {
+            return Err(Error::WrongPixelCount);
+        }
+
+        let end_x = (x as u32 + img_width - 1) as u16;
+        let end_y = (y as u32 + img_height - 1) as u16;
+
+        self.set_address_window(x, y, end_x, end_y)?;
+        self.write_command(Instruction::RamWr as u8, &[])?;
+        self.write_data(image_data)
+    }
+
+    /// Draws a full-screen image from run-length encoded RGB565 data.
+    ///
+    /// The data is a sequence of 3-byte runs: a count byte giving the number of
+    /// pixels in the run (runs with a count of 0 are skipped), followed by the
+    /// big-endian RGB565 color. Runs are decoded on the fly and streamed through the
+    /// transfer buffer, so no decode buffer is needed. A run may continue across the
+    /// end of a row.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Run-length encoded image data.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::WrongPixelCount` without sending anything unless `data` consists of
+    /// whole runs that add up to exactly one pixel per pixel of the display.
+    pub fn draw_image_rle(&mut self, data: &[u8]) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let pixels: usize = data.chunks(3).map(|run| run[0] as usize).sum();
+        if !data.len().is_multiple_of(3) || pixels != (self.width * self.height) as usize {
+            return Err(Error::WrongPixelCount);
+        }
+
+        self.set_address_window(0, 0, self.width as u16 - 1, self.height as u16 - 1)?;
+        self.write_pixels(data.chunks_exact(3).flat_map(|run| {
+            core::iter::repeat_n(u16::from_be_bytes([run[1], run[2]]), run[0] as usize)
+        }))
+    }
+
+    /// Draws a 1-bit bitmap, such as a font glyph or icon, in two colors.
+    ///
+    /// Each row of the bitmap starts on a new byte and is read most significant bit
+    /// first, so a row takes `width` bits rounded up to whole bytes. Set bits are
+    /// drawn in `fg` and clear bits in `bg`, streamed through the transfer buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the top-left corner.
+    /// * `y` - Y-coordinate of the top-left corner.
+    /// * `width` - Width of the bitmap in pixels.
+    /// * `height` - Height of the bitmap in pixels.
+    /// * `bitmap` - Bitmap data, row by row.
+    /// * `fg` - Color of set bits.
+    /// * `bg` - Color of clear bits.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. An empty bitmap sends nothing.
+    /// Returns `Error::OutOfBounds` if the bitmap does not fit on the display, or
+    /// `Error::BufferTooSmall` if `bitmap` is shorter than `height` rows.
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_mono<C: Color>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u32,
+        height: u32,
+        bitmap: &[u8],
+        fg: C,
+        bg: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        if x as u32 + width > self.width || y as u32 + height > self.height {
+            return Err(Error::OutOfBounds);
+        }
+        let stride = (width as usize).div_ceil(8);
+        if bitmap.len() < stride * height as usize {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let end_x = (x as u32 + width - 1) as u16;
+        let end_y = (y as u32 + height - 1) as u16;
+        self.set_pixels(
+            x,
+            y,
+            end_x,
+            end_y,
+            (0..height as usize)
+                .flat_map(|row| (0..width as usize).map(move |column| (column, row)))
+                .map(|(column, row)| {
+                    if mono_bit(bitmap, stride, column, row) {
+                        fg
+                    } else {
+                        bg
+                    }
+                }),
+        )
+    }
+
+    /// Draws the set bits of a 1-bit bitmap, leaving the pixels of clear bits as
+    /// they are.
+    ///
+    /// The bitmap layout is the same as for `blit_mono`. Each row is split into runs
+    /// of set bits, and each run is sent as its own span, so glyphs and icons can be
+    /// drawn over existing content.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the top-left corner.
+    /// * `y` - Y-coordinate of the top-left corner.
+    /// * `width` - Width of the bitmap in pixels.
+    /// * `height` - Height of the bitmap in pixels.
+    /// * `bitmap` - Bitmap data, row by row.
+    /// * `fg` - Color of set bits.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. An empty bitmap sends nothing.
+    /// Returns `Error::OutOfBounds` if the bitmap does not fit on the display, or
+    /// `Error::BufferTooSmall` if `bitmap` is shorter than `height` rows.
+    pub fn blit_mono_transparent<C: Color>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u32,
+        height: u32,
+        bitmap: &[u8],
+        fg: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        if x as u32 + width > self.width || y as u32 + height > self.height {
+            return Err(Error::OutOfBounds);
+        }
+        let stride = (width as usize).div_ceil(8);
+        if bitmap.len() < stride * height as usize {
+            return Err(Error::BufferTooSmall);
+        }
+
+        for row in 0..height as usize {
+            let mut column = 0;
+            while column < width as usize {
+                if !mono_bit(bitmap, stride, column, row) {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while column < width as usize && mono_bit(bitmap, stride, column, row) {
+                    column += 1;
+                }
+                self.fill_rect(
+                    x + start as u16,
+                    y + row as u16,
+                    (column - start) as u32,
+                    1,
+                    fg,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Displays the provided buffer on the screen.
+    ///
+    /// This function writes the entire buffer to the display, assuming the buffer
+    /// contains pixel data for the full display area. The full-screen address window
+    /// is set first, so the result does not depend on earlier drawing calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Buffer to display.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn show(&mut self, buffer: &[u8]) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.reset_address_window()?;
+        self.write_command(Instruction::RamWr as u8, &[])?;
+
+        self.write_data(buffer)?;
+
+        Ok(())
+    }
+
+    /// Updates the display with only the rows that differ between two frames.
+    ///
+    /// Both buffers must be full frames of the display size in the current pixel
+    /// format: `new` is the frame to show and `old` the frame currently on screen.
+    /// Rows are compared one by one, and each band of consecutive changed rows is
+    /// sent as one window. The caller swaps the buffers afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `new` - Frame to show.
+    /// * `old` - Frame currently shown.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::BufferTooSmall` if either buffer is shorter than a full frame.
+    pub fn present(
+        &mut self,
+        new: &[u8],
+        old: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let frame_len = self.bytes_per_frame();
+        if new.len() < frame_len || old.len() < frame_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let row_len = frame_len / self.height as usize;
+        let mut band_start = None;
+        for row in 0..=self.height as usize {
+            let changed = row < self.height as usize
+                && new[row * row_len..(row + 1) * row_len]
+                    != old[row * row_len..(row + 1) * row_len];
+            match (changed, band_start) {
+                (true, None) => band_start = Some(row),
+                (false, Some(start)) => {
+                    self.set_address_window(
+                        0,
+                        start as u16,
+                        self.width as u16 - 1,
+                        row as u16 - 1,
+                    )?;
+                    self.write_raw_pixels(&new[start * row_len..row * row_len])?;
+                    band_start = None;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows a display-wide window of a wider buffer, for software horizontal scrolling.
+    ///
+    /// `buffer` holds rows of `buffer_width` pixels, one per display row, in the
+    /// current pixel format. Each row is sent starting at column `x_offset` of the
+    /// buffer, so moving the offset each frame scrolls the content sideways, as for
+    /// a ticker. Without `wrap` the offset is clamped so the window stays inside the
+    /// buffer; with `wrap` it is taken modulo the buffer width and rows wrap around
+    /// to the buffer's first column.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Wide buffer holding one row per display row.
+    /// * `buffer_width` - Width of the buffer in pixels.
+    /// * `x_offset` - Buffer column shown at the left edge of the display.
+    /// * `wrap` - Whether to wrap around at the right edge of the buffer.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::OutOfBounds`
+    /// if the buffer is narrower than the display, or `Error::BufferTooSmall` if it
+    /// holds fewer rows than the display.
+    pub fn scroll_horizontal(
+        &mut self,
+        buffer: &[u8],
+        buffer_width: u32,
+        x_offset: u32,
+        wrap: bool,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if buffer_width < self.width {
+            return Err(Error::OutOfBounds);
+        }
+        let bytes_per_pixel = self.color_format.bytes_per_pixel();
+        let row_len = buffer_width as usize * bytes_per_pixel;
+        if buffer.len() < row_len * self.height as usize {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let offset = if wrap {
+            x_offset % buffer_width
+        } else {
+            x_offset.min(buffer_width - self.width)
+        };
+        let start = offset as usize * bytes_per_pixel;
+        let visible = self.width as usize * bytes_per_pixel;
+
+        self.set_address_window(0, 0, self.width as u16 - 1, self.height as u16 - 1)?;
+        self.write_command(Instruction::RamWr as u8, &[])?;
+        for row in buffer.chunks_exact(row_len).take(self.height as usize) {
+            // Past the right edge of the buffer, continue from its first column
+            let first = &row[start..row_len.min(start + visible)];
+            self.write_data(first)?;
+            if first.len() < visible {
+                self.write_data(&row[..visible - first.len()])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts sending a full-screen buffer in bands of `chunk_rows` rows.
+    ///
+    /// Nothing is sent until `step` is called on the returned [`ShowChunked`]; each
+    /// step then sends one band, like `show_region`, so a cooperative scheduler can
+    /// interleave a frame update with other work.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Full-screen buffer to display.
+    /// * `chunk_rows` - Number of rows sent per step; 0 is treated as 1.
+    ///
+    /// # Returns
+    ///
+    /// A [`ShowChunked`] stepper borrowing the driver and the buffer.
+    pub fn show_chunked<'b>(
+        &mut self,
+        buffer: &'b [u8],
+        chunk_rows: u16,
+    ) -> ShowChunked<'_, 'b, DI, RST, BL, BUF> {
+        ShowChunked {
+            display: self,
+            buffer,
+            chunk_rows: chunk_rows.max(1) as u32,
+            next_row: 0,
+        }
+    }
+
+    /// Updates only the specified region of the display with the provided buffer.
+    ///
+    /// This function updates a specified rectangular region of the display with the pixel data
+    /// provided in the buffer. It calculates the necessary offsets and addresses to update only
+    /// the designated area, ensuring efficient display refresh.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes representing the pixel data in RGB565 format.
+    /// * `top_left_x` - The x-coordinate of the top-left corner of the region to update.
+    /// * `top_left_y` - The y-coordinate of the top-left corner of the region to update.
+    /// * `width` - The width of the region to update.
+    /// * `height` - The height of the region to update.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success (`Ok`) or failure (`Err`). An empty region
+    /// sends nothing. Returns `Error::OutOfBounds` if the region extends past the display,
+    /// or `Error::BufferTooSmall` if the buffer does not cover the region.
+    pub fn show_region(
+        &mut self,
+        buffer: &[u8],
+        top_left_x: u16,
+        top_left_y: u16,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        if top_left_x as u32 + width > self.width || top_left_y as u32 + height > self.height {
+            return Err(Error::OutOfBounds);
+        }
+
+        let start_x = top_left_x; // Start x-coordinate
+        let start_y = top_left_y; // Start y-coordinate
+        let end_x = (top_left_x as u32 + width - 1) as u16; // End x-coordinate
+        let end_y = (top_left_y as u32 + height - 1) as u16; // End y-coordinate
+
+        // Calculate the buffer offset for the region
+        let buffer_width = self.width as usize; // Width of the buffer
+        let bytes_per_pixel = self.color_format.bytes_per_pixel(); // Bytes per pixel in the current format
+
+        // The last row of the region must lie within the buffer
+        let required_len = ((end_y as usize) * buffer_width + end_x as usize + 1) * bytes_per_pixel;
+        if buffer.len() < required_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        // Set the address window for the region to be updated
+        self.set_address_window(start_x, start_y, end_x, end_y)?;
+
+        // Send the command to write to RAM
+        self.write_command(Instruction::RamWr as u8, &[])?;
+
+        // Start data transmission
+
+        // Iterate over each row in the region
+        for y in start_y..=end_y {
+            let start_index = ((y as usize) * buffer_width + (start_x as usize)) * bytes_per_pixel;
+            let end_index = start_index + (width as usize) * bytes_per_pixel;
+
+            // Write each row in a single transfer
+            self.write_data(&buffer[start_index..end_index])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn store_region(
+        &mut self,
+        region: Region,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        for i in 0..self.regions.len() {
+            if self.regions[i].is_none() {
+                self.regions[i] = Some(region);
+                return Ok(());
+            }
+        }
+        Err(Error::RegionsFull)
+    }
+
+    pub fn store_region_from_params(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u32,
+        height: u32,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let region = Region {
+            x,
+            y,
+            width,
+            height,
+        };
+
+        self.store_region(region)
+    }
+
+    pub fn get_regions(&self) -> &[Option<Region>] {
+        &self.regions
+    }
+
+    pub fn clear_regions(&mut self) {
+        self.regions = [None; 10];
+    }
+
+    pub fn show_regions(&mut self, buffer: &[u8]) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        for i in 0..self.regions.len() {
+            if self.regions[i].is_some() {
+                if let Some(region_data) = self.regions[i] {
+                    self.show_region(
+                        buffer,
+                        region_data.x,
+                        region_data.y,
+                        region_data.width,
+                        region_data.height,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Additional function with default parameter
+    pub fn show_regions_and_clear(
+        &mut self,
+        buffer: &[u8],
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.show_regions(buffer)?;
+        self.clear_regions();
+        Ok(())
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> GC9A01A<DI, RST, BL, BUF>
+where
+    DI: ReadInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Reads the display identification.
+    ///
+    /// Useful to check at boot that the panel is connected and responding. Over
+    /// 4-wire SPI the controller sends one dummy clock before the 24-bit ID, which
+    /// is stripped here.
+    ///
+    /// # Returns
+    ///
+    /// The manufacturer ID, module/driver version ID and module/driver ID, or an
+    /// error if reading failed.
+    pub fn read_id(&mut self) -> Result<[u8; 3], Error<DI::PinError, DI::BusError>> {
+        let mut buffer = [0; 4];
+        self.pixel_cursor = None;
+        self.interface
+            .read_command(Instruction::RddId as u8, &mut buffer)?;
+        let id = u32::from_be_bytes(buffer) >> 7;
+        let [_, manufacturer, version, module] = id.to_be_bytes();
+        Ok([manufacturer, version, module])
+    }
+
+    /// Reads the 32-bit display status register (RDDST).
+    ///
+    /// As with `read_id`, the dummy clock sent before the status over 4-wire SPI is
+    /// stripped. The most significant bit reports whether the booster voltage is on.
+    ///
+    /// # Returns
+    ///
+    /// The display status, or an error if reading failed.
+    pub fn read_status(&mut self) -> Result<u32, Error<DI::PinError, DI::BusError>> {
+        let mut buffer = [0; 5];
+        self.pixel_cursor = None;
+        self.interface
+            .read_command(Instruction::RddSt as u8, &mut buffer)?;
+        let [first, rest @ ..] = buffer;
+        let status = (u32::from_be_bytes(rest) >> 7) | ((first as u32) << 25);
+        Ok(status)
+    }
+
+    /// Checks that the controller uses the pixel format set by `init` or
+    /// `set_color_format`.
+    ///
+    /// Reads back the pixel format register (RDDCOLMOD). Call it after `init` to
+    /// catch clone controllers that silently ignore the COLMOD write and garble
+    /// colors. As with `read_id`, the dummy clock sent over 4-wire SPI is stripped.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns
+    /// `Error::ColorFormatMismatch` if the controller reports a different format.
+    pub fn verify_color_format(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let mut buffer = [0; 2];
+        self.pixel_cursor = None;
+        self.interface
+            .read_command(Instruction::RddColMod as u8, &mut buffer)?;
+        let format = (u16::from_be_bytes(buffer) >> 7) as u8;
+        // Only the MCU interface format bits are set by COLMOD
+        if format & 0x07 != self.color_format as u8 & 0x07 {
+            return Err(Error::ColorFormatMismatch);
+        }
+        Ok(())
+    }
+
+    /// Polls the display status until the booster voltage is on.
+    ///
+    /// Can replace a worst-case fixed delay after reset or sleep out on panels
+    /// that report their status, and detects a panel that never comes up.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Delay provider, used to wait 1ms between polls.
+    /// * `timeout_ms` - Maximum time to wait, in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Returns `Error::Timeout` if
+    /// the display does not report ready within `timeout_ms`.
+    pub fn wait_until_ready<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        timeout_ms: u32,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>>
+    where
+        DELAY: DelayNs,
+    {
+        for _ in 0..=timeout_ms {
+            if self.read_status()? & STATUS_BOOSTER_ON != 0 {
+                return Ok(());
+            }
+            delay.delay_ms(1);
+        }
+        Err(Error::Timeout)
+    }
+}
+
+impl<DI, RST, BL, const BUF: usize> GC9A01A<DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+    BL: OutputPin<Error = DI::PinError>,
+{
+    /// Turns the backlight on.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn backlight_on(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.bl.set_high().map_err(Error::Pin)
+    }
+
+    /// Turns the backlight off.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn backlight_off(&mut self) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.bl.set_low().map_err(Error::Pin)
+    }
+}
+
+/// Returns whether the pixel at `(x, y)` of a 1-bit bitmap with rows of `stride`
+/// bytes is set, reading each byte most significant bit first.
+fn mono_bit(bitmap: &[u8], stride: usize, x: usize, y: usize) -> bool {
+    bitmap[y * stride + x / 8] & (0x80 >> (x % 8)) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::cell::RefCell;
+    use core::convert::Infallible;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    /// SPI bus that records every write into a shared log.
+    #[derive(Default)]
+    struct RecordingSpi {
+        writes: Rc<RefCell<Vec<Vec<u8>>>>,
+        /// Bytes returned by reads.
+        response: Vec<u8>,
+    }
+
+    impl embedded_hal::spi::ErrorType for RecordingSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiBus<u8> for RecordingSpi {
+        fn read(&mut self, words: &mut [u8]) -> Result<(), Infallible> {
+            for (word, byte) in words.iter_mut().zip(&self.response) {
+                *word = *byte;
+            }
+            Ok(())
+        }
+
+        fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+            self.writes.borrow_mut().push(words.to_vec());
+            Ok(())
+        }
+
+        fn transfer(&mut self, _read: &mut [u8], write: &[u8]) -> Result<(), Infallible> {
+            self.write(write)
+        }
+
+        fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::spi::SpiBus<u8> for RecordingSpi {
+        async fn read(&mut self, words: &mut [u8]) -> Result<(), Infallible> {
+            SpiBus::read(self, words)
+        }
+
+        async fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+            SpiBus::write(self, words)
+        }
+
+        async fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Infallible> {
+            SpiBus::transfer(self, read, write)
+        }
+
+        async fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Infallible> {
+            SpiBus::transfer_in_place(self, words)
+        }
+
+        async fn flush(&mut self) -> Result<(), Infallible> {
+            SpiBus::flush(self)
+        }
+    }
+
+    /// Runs a future to completion, for the async driver whose mocks never wait.
+    #[cfg(feature = "async")]
+    fn block_on<F: core::future::Future>(future: F) -> F::Output {
+        let mut future = core::pin::pin!(future);
+        let mut context = core::task::Context::from_waker(core::task::Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    /// Output pin that ignores all writes.
+    struct NoopPin;
+
+    impl embedded_hal::digital::ErrorType for NoopPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for NoopPin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    /// Delay provider that returns immediately.
+    struct NoopDelay;
+
+    impl DelayNs for NoopDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    type TestDisplay = GC9A01A<SpiInterface<RecordingSpi, NoopPin, NoopPin>, NoopPin>;
+
+    /// Driver on a recording bus, awake as after `init` but without its traffic.
+    fn display() -> (TestDisplay, Rc<RefCell<Vec<Vec<u8>>>>) {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display = GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 240, 240).unwrap();
+        display.sleeping = false;
+        (display, writes)
+    }
+
+    #[test]
+    fn write_colors_buffered_sends_exactly_the_input_words() {
+        for count in [1u16, 15, 16, 17, 33] {
+            let words: Vec<u16> = (0..count).map(|i| 0xA500 | i).collect();
+            let expected: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+
+            let (mut display, writes) = display();
+            display
+                .write_colors_buffered(words.iter().copied())
+                .unwrap();
+
+            let writes = writes.borrow();
+            assert!(
+                writes.iter().all(|write| !write.is_empty()),
+                "count {count}"
+            );
+            assert!(
+                writes
+                    .iter()
+                    .all(|write| write.len() <= DEFAULT_BUFFER_SIZE),
+                "count {count}"
+            );
+            assert_eq!(writes.concat(), expected, "count {count}");
+        }
+    }
+
+    #[test]
+    fn read_id_strips_the_dummy_clock() {
+        let spi = RecordingSpi {
+            response: std::vec![0x00, 0x81, 0x81, 0x80],
+            ..Default::default()
+        };
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 240, 240).unwrap();
+
+        assert_eq!(display.read_id().unwrap(), [0x01, 0x03, 0x03]);
+        assert_eq!(writes.borrow().concat(), [0x04]);
+    }
+
+    #[test]
+    fn read_status_strips_the_dummy_clock() {
+        let spi = RecordingSpi {
+            response: std::vec![0x40, 0x00, 0x00, 0x01, 0x80],
+            ..Default::default()
+        };
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 240, 240).unwrap();
+
+        assert_eq!(display.read_status().unwrap(), 0x8000_0003);
+        assert_eq!(display.wait_until_ready(&mut NoopDelay, 10), Ok(()));
+        assert_eq!(writes.borrow().concat(), [0x09, 0x09]);
+    }
+
+    #[test]
+    fn wait_until_ready_times_out() {
+        let (mut display, writes) = display();
+
+        assert_eq!(
+            display.wait_until_ready(&mut NoopDelay, 2),
+            Err(Error::Timeout)
+        );
+        assert_eq!(writes.borrow().len(), 3);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn show_dirty_merges_overlapping_rectangles() {
+        use embedded_graphics_core::prelude::{Point, Size};
+        use embedded_graphics_core::primitives::Rectangle;
+
+        let (mut display, writes) = display();
+        let buffer = std::vec![0; 240 * 240 * 2];
+        let dirty = [
+            Rectangle::new(Point::new(0, 0), Size::new(10, 10)),
+            Rectangle::new(Point::new(100, 100), Size::new(2, 2)),
+            Rectangle::new(Point::new(5, 5), Size::new(10, 10)),
+            Rectangle::new(Point::new(230, 230), Size::new(20, 20)),
+        ];
+        display.show_dirty(&buffer, &dirty).unwrap();
+
+        let writes = writes.borrow();
+        let memory_writes = writes.iter().filter(|write| write[..] == [0x2C]).count();
+        assert_eq!(memory_writes, 3);
+        // Each region costs CASET, RASET and RAMWR with their parameters
+        let total_bytes: usize = writes.iter().map(Vec::len).sum();
+        assert_eq!(
+            total_bytes - memory_writes * 11,
+            (15 * 15 + 2 * 2 + 10 * 10) * 2
+        );
+    }
+
+    #[test]
+    fn show_region_sends_nothing_for_an_empty_region() {
+        let (mut display, writes) = display();
+        let buffer = std::vec![0; 240 * 240 * 2];
+
+        display.show_region(&buffer, 10, 10, 0, 5).unwrap();
+        display.show_region(&buffer, 10, 10, 5, 0).unwrap();
+        assert!(writes.borrow().is_empty());
+    }
+
+    #[test]
+    fn show_region_accepts_a_region_at_the_display_edge() {
+        let (mut display, writes) = display();
+        let buffer = std::vec![0; 240 * 240 * 2];
+
+        display.show_region(&buffer, 230, 235, 10, 5).unwrap();
+        let pixel_bytes: usize = writes
+            .borrow()
+            .iter()
+            .filter(|write| write.len() > 4)
+            .map(Vec::len)
+            .sum();
+        assert_eq!(pixel_bytes, 10 * 5 * 2);
+    }
+
+    #[test]
+    fn show_region_reads_rgb666_rows_at_three_bytes_per_pixel() {
+        let (mut display, writes) = display();
+        display.set_color_format(ColorFormat::Rgb666).unwrap();
+        let buffer: Vec<u8> = (0..240 * 3 * 3).map(|i| i as u8).collect();
+
+        writes.borrow_mut().clear();
+        display.show_region(&buffer, 1, 1, 2, 2).unwrap();
+
+        let row = |y: usize| -> Vec<u8> {
+            let start = (y * 240 + 1) * 3;
+            buffer[start..start + 6].to_vec()
+        };
+        let sent = writes.borrow();
+        assert_eq!(sent[sent.len() - 2..], [row(1), row(2)]);
+        drop(sent);
+
+        // The last row ends one pixel short
+        assert_eq!(
+            display.show_region(&buffer[..(2 * 240 + 2) * 3], 1, 1, 2, 2),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn show_region_rejects_oversized_regions() {
+        let (mut display, writes) = display();
+        let buffer = std::vec![0; 240 * 240 * 2];
+
+        assert_eq!(
+            display.show_region(&buffer, 230, 0, 11, 5),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            display.show_region(&buffer, 0, 0, 10, u32::MAX),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            display.show_region(&buffer, u16::MAX, 0, 1, 1),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            display.show_region(&buffer[..100], 0, 0, 240, 240),
+            Err(Error::BufferTooSmall)
+        );
+        assert!(writes.borrow().is_empty());
+    }
+
+    /// Pin change or SPI write, in the order they happened.
+    #[derive(Clone, Debug, PartialEq)]
+    enum Event {
+        Cs(bool),
+        Dc(bool),
+        Rst(bool),
+        Write(Vec<u8>),
+    }
+
+    type EventLog = Rc<RefCell<Vec<Event>>>;
+
+    /// SPI bus that records writes into an event log shared with the pins.
+    struct EventSpi(EventLog);
+
+    impl embedded_hal::spi::ErrorType for EventSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiBus<u8> for EventSpi {
+        fn read(&mut self, _words: &mut [u8]) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+            self.0.borrow_mut().push(Event::Write(words.to_vec()));
+            Ok(())
+        }
+
+        fn transfer(&mut self, _read: &mut [u8], write: &[u8]) -> Result<(), Infallible> {
+            self.write(write)
+        }
+
+        fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    /// Output pin that records its level changes into an event log.
+    struct EventPin(EventLog, fn(bool) -> Event);
+
+    impl embedded_hal::digital::ErrorType for EventPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for EventPin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.0.borrow_mut().push((self.1)(false));
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.0.borrow_mut().push((self.1)(true));
+            Ok(())
+        }
+    }
+
+    fn event_interface() -> (SpiInterface<EventSpi, EventPin, EventPin>, EventLog) {
+        let log = EventLog::default();
+        let interface = SpiInterface::new(
+            EventSpi(log.clone()),
+            EventPin(log.clone(), Event::Dc),
+            EventPin(log.clone(), Event::Cs),
+        );
+        (interface, log)
+    }
+
+    type EventDisplay = GC9A01A<SpiInterface<EventSpi, EventPin, EventPin>, EventPin>;
+
+    fn event_display() -> (EventDisplay, EventLog) {
+        let (interface, log) = event_interface();
+        let rst = EventPin(log.clone(), Event::Rst);
+        let mut display = GC9A01A::with_interface(interface, rst, false, 240, 240).unwrap();
+        display.sleeping = false;
+        (display, log)
+    }
+
+    /// Events of a command with its parameters sent in one transaction.
+    fn command_events(command: u8, params: &[u8]) -> Vec<Event> {
+        let mut events = std::vec![
+            Event::Dc(false),
+            Event::Cs(false),
+            Event::Write(std::vec![command])
+        ];
+        if !params.is_empty() {
+            events.extend([Event::Dc(true), Event::Write(params.to_vec())]);
+        }
+        events.push(Event::Cs(true));
+        events
+    }
+
+    /// Events of a data transfer.
+    fn data_events(data: &[u8]) -> Vec<Event> {
+        std::vec![
+            Event::Dc(true),
+            Event::Cs(false),
+            Event::Write(data.to_vec()),
+            Event::Cs(true),
+        ]
+    }
+
+    #[test]
+    fn set_address_window_sends_offset_coordinates() {
+        let (mut display, log) = event_display();
+        display.set_offset(2, 3);
+        display.set_address_window(10, 20, 0x110, 30).unwrap_err();
+        assert!(log.borrow().is_empty());
+
+        display.set_address_window(10, 20, 100, 30).unwrap();
+        let expected = [
+            command_events(0x2A, &[0x00, 12, 0x00, 102]),
+            command_events(0x2B, &[0x00, 23, 0x00, 33]),
+        ]
+        .concat();
+        assert_eq!(*log.borrow(), expected);
+    }
+
+    #[test]
+    fn write_pixel_sets_a_window_to_the_row_end_and_writes_the_color() {
+        let (mut display, log) = event_display();
+        display.write_pixel(3, 4, 0xF800).unwrap();
+
+        let expected = [
+            command_events(0x2A, &[0x00, 3, 0x00, 239]),
+            command_events(0x2B, &[0x00, 4, 0x00, 4]),
+            command_events(0x2C, &[]),
+            data_events(&[0xF8, 0x00]),
+        ]
+        .concat();
+        assert_eq!(*log.borrow(), expected);
+    }
+
+    #[test]
+    fn init_resets_and_sends_the_init_sequence() {
+        let (mut display, log) = event_display();
+        display.init(&mut NoopDelay).unwrap();
+
+        // Commands of a batch share one CS-low transaction, with only DC toggling
+        let batch_events = |commands: &[(u8, &[u8])]| {
+            let mut events = std::vec![Event::Cs(false)];
+            for (command, params) in commands {
+                events.extend([Event::Dc(false), Event::Write(std::vec![*command])]);
+                if !params.is_empty() {
+                    events.extend([Event::Dc(true), Event::Write(params.to_vec())]);
+                }
+            }
+            events.push(Event::Cs(true));
+            events
+        };
+        // MADCTL and COLMOD are sent on their own, as the driver adjusts them, and so
+        // are SLPOUT and DISPON, which are followed by a delay
+        let madctl = INIT_SEQUENCE
+            .iter()
+            .position(|(command, _)| *command == Instruction::MadCtl as u8)
+            .unwrap();
+        assert_eq!(INIT_SEQUENCE[madctl + 1].0, Instruction::ColMod as u8);
+
+        let log = log.borrow();
+        assert_eq!(
+            log[..3],
+            [Event::Rst(true), Event::Rst(false), Event::Rst(true)]
+        );
+        let expected = [
+            batch_events(&INIT_SEQUENCE[..madctl]),
+            command_events(0x36, &[0x98]),
+            command_events(0x3A, &[0x05]),
+            batch_events(&INIT_SEQUENCE[madctl + 2..INIT_SEQUENCE.len() - 2]),
+            command_events(0x11, &[]),
+            command_events(0x29, &[]),
+        ]
+        .concat();
+        assert_eq!(log[3..], expected);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn clear_streams_the_color_over_the_whole_display() {
+        use embedded_graphics_core::pixelcolor::Rgb565;
+        use embedded_graphics_core::prelude::RgbColor;
+
+        let (mut display, writes) = display();
+        display.clear(Rgb565::RED).unwrap();
+
+        let writes = writes.borrow();
+        assert_eq!(
+            writes[..4].concat(),
+            [0x2A, 0, 0, 0, 0xEF, 0x2B, 0, 0, 0, 0xEF]
+        );
+        assert_eq!(writes[4], [0x2C]);
+        assert!(writes[5..].iter().all(|write| write.len() <= 512 * 2));
+        assert_eq!(writes[5..].concat(), [0xF8, 0x00].repeat(240 * 240));
+    }
+
+    #[test]
+    fn command_without_params_is_a_single_transaction() {
+        let (mut interface, log) = event_interface();
+        interface.write_command(0x29, &[]).unwrap();
+
+        assert_eq!(
+            *log.borrow(),
+            [
+                Event::Dc(false),
+                Event::Cs(false),
+                Event::Write(std::vec![0x29]),
+                Event::Cs(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn command_with_params_is_a_single_transaction() {
+        let (mut interface, log) = event_interface();
+        interface.write_command(0x36, &[0x48, 0x01]).unwrap();
+
+        assert_eq!(
+            *log.borrow(),
+            [
+                Event::Dc(false),
+                Event::Cs(false),
+                Event::Write(std::vec![0x36]),
+                Event::Dc(true),
+                Event::Write(std::vec![0x48, 0x01]),
+                Event::Cs(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn circle_span_covers_the_inscribed_circle() {
+        let (display, _) = display();
+
+        assert_eq!(display.circle_span(0), Some((109, 130)));
+        assert_eq!(display.circle_span(119), Some((0, 239)));
+        assert_eq!(display.circle_span(120), Some((0, 239)));
+        assert_eq!(display.circle_span(239), Some((109, 130)));
+    }
+
+    #[test]
+    fn set_mirror_keeps_rotation_and_color_order() {
+        let (mut display, writes) = display();
+        display.set_orientation(&Orientation::Landscape).unwrap();
+        display.set_mirror(true, false).unwrap();
+        display.set_mirror(true, true).unwrap();
+
+        assert_eq!(
+            *writes.borrow(),
+            [
+                std::vec![0x36],
+                std::vec![0x68],
+                std::vec![0x36],
+                std::vec![0x28],
+                std::vec![0x36],
+                std::vec![0xA8],
+            ]
+        );
+        assert_eq!(display.orientation(), Some(Orientation::Landscape));
+    }
+
+    #[test]
+    fn set_mirror_after_init_keeps_the_init_rotation() {
+        let (mut display, _writes) = display();
+        display.init(&mut NoopDelay).unwrap();
+        display.set_mirror(false, false).unwrap();
+        assert_eq!(display.madctl(), MADCTL_MY | MADCTL_ML | MADCTL_BGR);
+        display.set_mirror(true, false).unwrap();
+        assert_eq!(
+            display.madctl(),
+            MADCTL_MY | MADCTL_MX | MADCTL_ML | MADCTL_BGR
+        );
+
+        // Mirroring set before init is applied to the init table's value
+        let (mut fresh, _writes) = self::display();
+        fresh.set_mirror(false, true).unwrap();
+        fresh.init(&mut NoopDelay).unwrap();
+        assert_eq!(fresh.madctl(), MADCTL_ML | MADCTL_BGR);
+    }
+
+    #[test]
+    fn rgb888_is_reduced_to_rgb565() {
+        assert_eq!(colors::rgb565(0xFF, 0xFF, 0xFF), 0xFFFF);
+        assert_eq!(colors::rgb565(0xFF, 0x00, 0x00), 0xF800);
+        assert_eq!(colors::rgb565(0x00, 0xFF, 0x00), 0x07E0);
+        assert_eq!(colors::rgb565(0x00, 0x00, 0xFF), 0x001F);
+        assert_eq!(colors::rgb565(0x12, 0x34, 0x56), 0x11AA);
+    }
+
+    #[test]
+    fn blend565_mixes_each_channel() {
+        const HALF_GRAY: u16 = colors::blend565(colors::WHITE, colors::BLACK, 128);
+        assert_eq!(HALF_GRAY, 0x8410);
+        assert_eq!(
+            colors::blend565(colors::RED, colors::BLUE, 255),
+            colors::RED
+        );
+        assert_eq!(colors::blend565(colors::RED, colors::BLUE, 0), colors::BLUE);
+        assert_eq!(colors::blend565(colors::RED, colors::GREEN, 128), 0x83E0);
+    }
+
+    #[test]
+    fn draw_image_sends_the_image_in_one_transfer() {
+        let (mut display, writes) = display();
+        let image = std::vec![0x5A; 240 * 240 * 2];
+        display.draw_image(&image).unwrap();
+
+        let writes = writes.borrow();
+        assert_eq!(writes[writes.len() - 2], [0x2C]);
+        assert_eq!(writes[writes.len() - 1], image);
+    }
+
+    #[test]
+    fn draw_image_rejects_data_of_the_wrong_length() {
+        let (mut display, writes) = display();
+        // One byte per pixel instead of two
+        let image = std::vec![0x5A; 240 * 240];
+        assert_eq!(display.draw_image(&image), Err(Error::WrongPixelCount));
+        assert_eq!(
+            display.draw_image_at(0, 0, 2, 2, &[0; 9]),
+            Err(Error::WrongPixelCount)
+        );
+        assert!(writes.borrow().is_empty());
+    }
+
+    #[test]
+    fn offset_follows_the_panel_when_rotating() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, true, 200, 220).unwrap();
+        display.set_offset(10, 4);
+
+        let window_start = |display: &mut TestDisplay, orientation| {
+            display.set_orientation(&orientation).unwrap();
+            writes.borrow_mut().clear();
+            display.set_address_window(0, 0, 0, 0).unwrap();
+            let writes = writes.borrow();
+            (
+                u16::from_be_bytes([writes[1][0], writes[1][1]]),
+                u16::from_be_bytes([writes[3][0], writes[3][1]]),
+            )
+        };
+
+        assert_eq!(window_start(&mut display, Orientation::Portrait), (10, 4));
+        assert_eq!(window_start(&mut display, Orientation::Landscape), (4, 30));
+        assert_eq!(
+            window_start(&mut display, Orientation::PortraitSwapped),
+            (30, 16)
+        );
+        assert_eq!(
+            window_start(&mut display, Orientation::LandscapeSwapped),
+            (16, 10)
+        );
+
+        display.set_offset(1, 2);
+        assert_eq!(
+            window_start(&mut display, Orientation::LandscapeSwapped),
+            (1, 2)
+        );
+        assert_eq!(window_start(&mut display, Orientation::Portrait), (2, 19));
+    }
+
+    #[test]
+    fn fill_circle_sends_one_span_per_row() {
+        let (mut display, writes) = display();
+        display.fill_circle(2, 100, 3, 0xFFFF).unwrap();
+
+        // The middle row is clipped at the left edge of the display
+        let writes = writes.borrow();
+        let widths: Vec<usize> = writes
+            .windows(2)
+            .filter(|pair| pair[0] == [0x2C])
+            .map(|pair| pair[1].len() / 2)
+            .collect();
+        assert_eq!(widths, [1, 5, 5, 6, 5, 5, 1]);
+    }
+
+    #[test]
+    fn set_pixels_checked_rejects_mismatched_lengths() {
+        let (mut display, writes) = display();
+        let sent_pixels = |writes: &Rc<RefCell<Vec<Vec<u8>>>>| {
+            let writes = writes.borrow();
+            let start = writes.iter().position(|write| write[..] == [0x2C]).unwrap();
+            writes[start + 1..].concat().len() / 2
+        };
+
+        assert_eq!(
+            display.set_pixels_checked(0, 0, 9, 9, core::iter::repeat_n(0xFFFF, 50)),
+            Err(Error::WrongPixelCount)
+        );
+        assert_eq!(sent_pixels(&writes), 50);
+
+        writes.borrow_mut().clear();
+        assert_eq!(
+            display.set_pixels_checked(0, 0, 9, 9, core::iter::repeat_n(0xFFFF, 150)),
+            Err(Error::WrongPixelCount)
+        );
+        assert_eq!(sent_pixels(&writes), 100);
+
+        writes.borrow_mut().clear();
+        assert_eq!(
+            display.set_pixels_checked(0, 0, 9, 9, core::iter::repeat_n(0xFFFF, 100)),
+            Ok(())
+        );
+        assert_eq!(sent_pixels(&writes), 100);
+    }
+
+    #[test]
+    fn init_without_reset_pin_uses_software_reset() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display =
+            GC9A01A::new_without_reset(spi, NoopPin, NoopPin, false, 240, 240).unwrap();
+        display.init(&mut NoopDelay).unwrap();
+
+        assert_eq!(writes.borrow()[0], [0x01]);
+        assert!(display.release().1.is_none());
+    }
+
+    #[test]
+    fn set_address_window_rejects_offsets_past_frame_memory() {
+        let (mut display, writes) = display();
+
+        display.set_offset(u16::MAX, 0);
+        assert_eq!(
+            display.set_address_window(5, 0, 10, 0),
+            Err(Error::OutOfBounds)
+        );
+
+        display.set_offset(0, 1);
+        assert_eq!(
+            display.set_address_window(0, 0, 0, 239),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(display.set_address_window(0, 0, 0, 238), Ok(()));
+        assert_eq!(writes.borrow().len(), 4);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_set_address_window_rejects_offsets_past_frame_memory() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display =
+            asynch::GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 240, 240).unwrap();
+
+        display.set_offset(u16::MAX, 0);
+        assert_eq!(
+            block_on(display.set_address_window(5, 0, 10, 0)),
+            Err(Error::OutOfBounds)
+        );
+
+        display.set_offset(0, 1);
+        assert_eq!(
+            block_on(display.set_address_window(0, 0, 0, 239)),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(block_on(display.set_address_window(0, 0, 0, 238)), Ok(()));
+        assert_eq!(
+            writes.borrow().concat(),
+            [0x2A, 0, 0, 0, 0, 0x2B, 0, 1, 0, 239]
+        );
+    }
+
+    #[test]
+    fn rgb666_sends_three_bytes_per_pixel() {
+        let (mut display, writes) = display();
+        display.set_color_format(ColorFormat::Rgb666).unwrap();
+        assert_eq!(writes.borrow().concat(), [0x3A, 0x06]);
+
+        writes.borrow_mut().clear();
+        display
+            .write_pixels([0xFFFF, 0xF800, 0x07E0, 0x001F])
+            .unwrap();
+        assert_eq!(
+            writes.borrow()[1..].concat(),
+            [0xFC, 0xFC, 0xFC, 0xFC, 0x00, 0x00, 0x00, 0xFC, 0x00, 0x00, 0x00, 0xFC]
+        );
+    }
+
+    #[test]
+    fn rgb666_colors_are_encoded_for_the_current_format() {
+        let color = colors::Rgb666::new(0x3F, 0x20, 0x01);
+
+        let (mut display, writes) = display();
+        display.write_pixels([color]).unwrap();
+        assert_eq!(writes.borrow()[1..].concat(), [0xFC, 0x00]);
+
+        display.set_color_format(ColorFormat::Rgb666).unwrap();
+        writes.borrow_mut().clear();
+        display.write_pixels([color]).unwrap();
+        assert_eq!(writes.borrow()[1..].concat(), [0xFC, 0x80, 0x04]);
+    }
+
+    #[test]
+    fn show_chunked_sends_one_band_per_step() {
+        let (mut display, writes) = display();
+        let buffer = [0u8; 240 * 240 * 2];
+
+        let mut show = display.show_chunked(&buffer, 100);
+        assert_eq!(show.step(), Ok(Poll::Pending));
+        assert_eq!(show.step(), Ok(Poll::Pending));
+        assert_eq!(show.step(), Ok(Poll::Ready(())));
+        assert_eq!(show.step(), Ok(Poll::Ready(())));
+
+        let row_addresses: Vec<Vec<u8>> = writes
+            .borrow()
+            .windows(2)
+            .filter(|window| window[0] == [0x2B])
+            .map(|window| window[1].clone())
+            .collect();
+        assert_eq!(
+            row_addresses,
+            [[0, 0, 0, 99], [0, 100, 0, 199], [0, 200, 0, 239]]
+        );
+    }
+
+    #[test]
+    fn init_sends_madctl_with_the_color_order_of_the_rgb_flag() {
+        for (rgb, expected) in [(true, 0x90), (false, 0x98)] {
+            let spi = RecordingSpi::default();
+            let writes = spi.writes.clone();
+            let mut display = GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, rgb, 240, 240).unwrap();
+            display.init(&mut NoopDelay).unwrap();
+
+            let writes = writes.borrow();
+            let madctl = writes.iter().position(|write| write[..] == [0x36]).unwrap();
+            assert_eq!(writes[madctl + 1], [expected], "rgb {rgb}");
+        }
+
+        let (mut display, writes) = display();
+        display.set_color_order(true).unwrap();
+        assert_eq!(writes.borrow().concat(), [0x36, 0x00]);
+        display.set_color_order(false).unwrap();
+        assert_eq!(writes.borrow()[2..].concat(), [0x36, 0x08]);
+    }
+
+    #[test]
+    fn set_color_order_after_init_keeps_the_init_rotation() {
+        let (mut display, writes) = display();
+        display.init(&mut NoopDelay).unwrap();
+        assert_eq!(display.madctl(), 0x98);
+
+        writes.borrow_mut().clear();
+        display.set_color_order(true).unwrap();
+        assert_eq!(display.madctl(), MADCTL_MY | MADCTL_ML);
+        display.set_color_order(false).unwrap();
+        assert_eq!(display.madctl(), MADCTL_MY | MADCTL_ML | MADCTL_BGR);
+        assert_eq!(writes.borrow().concat(), [0x36, 0x90, 0x36, 0x98]);
+    }
+
+    #[test]
+    fn little_endian_pixel_order_swaps_pixel_bytes_only() {
+        let (mut display, writes) = display();
+        display.set_pixel_order(PixelOrder::LittleEndian);
+        display.set_pixels(1, 2, 2, 2, [0xF800, 0x001F]).unwrap();
+        display.write_pixel(3, 4, 0x07E0).unwrap();
+
+        let writes = writes.borrow();
+        assert_eq!(writes[1], [0x00, 0x01, 0x00, 0x02]);
+        assert_eq!(writes[5], [0x00, 0xF8, 0x1F, 0x00]);
+        assert_eq!(writes[11], [0xE0, 0x07]);
+    }
+
+    #[test]
+    fn getters_follow_the_orientation() {
+        let spi = RecordingSpi::default();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, true, 200, 220).unwrap();
+        display.set_offset(10, 4);
+        assert_eq!((display.width(), display.height()), (200, 220));
+        assert_eq!(display.offset(), (10, 4));
+
+        display.set_orientation(&Orientation::Landscape).unwrap();
+        assert_eq!((display.width(), display.height()), (220, 200));
+        assert_eq!(display.offset(), (4, 30));
+    }
+
+    #[test]
+    fn fill_rect_clipped_fills_only_the_visible_part() {
+        let (mut display, writes) = display();
+        display.fill_rect_clipped(-5, 238, 10, 10, 0xFFFF).unwrap();
+        display.fill_rect_clipped(-20, 0, 20, 5, 0xFFFF).unwrap();
+        display.fill_rect_clipped(240, 0, 5, 5, 0xFFFF).unwrap();
+
+        let writes = writes.borrow();
+        assert_eq!(
+            writes[..4].concat(),
+            [0x2A, 0, 0, 0, 4, 0x2B, 0, 238, 0, 239]
+        );
+        assert_eq!(writes[4], [0x2C]);
+        assert_eq!(writes[5..].concat(), [0xFF; 20]);
+    }
+
+    #[test]
+    fn set_pixels_bytes_sends_the_bytes_verbatim() {
+        let (mut display, writes) = display();
+        let data = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
+
+        assert_eq!(
+            display.set_pixels_bytes(0, 0, 1, 1, &data[..6]),
+            Err(Error::WrongPixelCount)
+        );
+        assert!(writes.borrow().is_empty());
+
+        assert_eq!(display.set_pixels_bytes(0, 0, 1, 1, &data), Ok(()));
+        let writes = writes.borrow();
+        assert_eq!(writes[4], [0x2C]);
+        assert_eq!(writes[5], data);
+        assert_eq!(writes.len(), 6);
+    }
+
+    #[test]
+    fn hard_reset_uses_the_given_timing() {
+        /// Delay provider that records each requested delay.
+        struct RecordingDelay(Vec<u32>);
+
+        impl DelayNs for RecordingDelay {
+            fn delay_ns(&mut self, ns: u32) {
+                self.0.push(ns);
+            }
+        }
+
+        let (mut display, log) = event_display();
+        let mut delay = RecordingDelay(Vec::new());
+        display.hard_reset_with_timing(&mut delay, 20, 50).unwrap();
+        assert_eq!(delay.0, [20_000, 20_000, 50_000_000]);
+
+        delay.0.clear();
+        display.hard_reset(&mut delay).unwrap();
+        assert_eq!(delay.0, [10_000, 10_000, 120_000_000]);
+        assert_eq!(
+            log.borrow()[..3],
+            [Event::Rst(true), Event::Rst(false), Event::Rst(true)]
+        );
+    }
+
+    #[test]
+    fn pixels_are_never_split_across_transfers() {
+        let (small, writes) = display();
+        let mut small = small.with_buffer_size::<5>();
+        small.write_pixels([0x1234u16; 5]).unwrap();
+        assert_eq!(writes.borrow()[1..].concat(), [0x12, 0x34].repeat(5));
+        assert!(writes.borrow()[1..]
+            .iter()
+            .all(|write| write.len() % 2 == 0));
+
+        let (tiny, writes) = display();
+        let mut tiny = tiny.with_buffer_size::<3>();
+        tiny.set_color_format(ColorFormat::Rgb666).unwrap();
+        writes.borrow_mut().clear();
+        tiny.write_pixels([0xFFFFu16; 2]).unwrap();
+        assert_eq!(writes.borrow()[1..], [[0xFC; 3], [0xFC; 3]]);
+    }
+
+    #[test]
+    fn blit_mono_expands_padded_rows() {
+        let (mut display, writes) = display();
+        // 10 pixels wide, so each row takes two bytes
+        let bitmap = [0b1000_0000, 0b0100_0000, 0b0000_0001, 0b1000_0000];
+
+        assert_eq!(
+            display.blit_mono(0, 0, 10, 2, &bitmap[..3], 0xFFFFu16, 0),
+            Err(Error::BufferTooSmall)
+        );
+        assert_eq!(
+            display.blit_mono(0, 0, 10, 2, &bitmap, 0xFFFFu16, 0),
+            Ok(())
+        );
+
+        let pixels: Vec<u16> = writes.borrow()[5..]
+            .concat()
+            .chunks(2)
+            .map(|pixel| u16::from_be_bytes([pixel[0], pixel[1]]))
+            .collect();
+        let mut expected = std::vec![0u16; 20];
+        for index in [0, 9, 17, 18] {
+            expected[index] = 0xFFFF;
+        }
+        assert_eq!(pixels, expected);
+    }
+
+    #[test]
+    fn blit_mono_transparent_sends_only_runs_of_set_bits() {
+        let (mut display, writes) = display();
+        let bitmap = [0b1100_0100, 0b0000_0000];
+        display
+            .blit_mono_transparent(10, 20, 8, 2, &bitmap, 0xFFFFu16)
+            .unwrap();
+
+        let runs: Vec<Vec<u8>> = writes
+            .borrow()
+            .windows(2)
+            .filter(|window| window[0] == [0x2A])
+            .map(|window| window[1].clone())
+            .collect();
+        assert_eq!(runs, [[0, 10, 0, 11], [0, 15, 0, 15]]);
+    }
+
+    #[test]
+    fn init_writes_the_configured_color_format() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display = GC9A01A::builder(spi, NoopPin, NoopPin, NoopPin)
+            .color_format(ColorFormat::Rgb666)
+            .build()
+            .unwrap();
+        display.init(&mut NoopDelay).unwrap();
+
+        let writes = writes.borrow();
+        let colmods: Vec<&Vec<u8>> = writes
+            .iter()
+            .zip(writes.iter().skip(1))
+            .filter(|(command, _)| command[..] == [0x3A])
+            .map(|(_, param)| param)
+            .collect();
+        assert_eq!(colmods, [&std::vec![0x06]]);
+        assert_eq!(display.color_format(), ColorFormat::Rgb666);
+    }
+
+    #[test]
+    fn scroll_horizontal_sends_the_shifted_window() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 2, 1).unwrap();
+        display.sleeping = false;
+        let buffer = [0, 1, 0, 2, 0, 3];
+
+        display.scroll_horizontal(&buffer, 3, 2, false).unwrap();
+        assert_eq!(writes.borrow()[5..].concat(), [0, 2, 0, 3]);
+
+        writes.borrow_mut().clear();
+        display.scroll_horizontal(&buffer, 3, 2, true).unwrap();
+        assert_eq!(writes.borrow()[5..], [[0, 3], [0, 1]]);
+
+        assert_eq!(
+            display.scroll_horizontal(&buffer, 1, 0, false),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            display.scroll_horizontal(&buffer[..4], 3, 0, false),
+            Err(Error::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn init_applies_the_inversion_setting() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display = GC9A01A::builder(spi, NoopPin, NoopPin, NoopPin)
+            .invert(false)
+            .build()
+            .unwrap();
+        assert!(!display.inverted());
+        display.init(&mut NoopDelay).unwrap();
+
+        // INVOFF follows the table, which ends with DISPON
+        let writes = writes.borrow();
+        assert_eq!(writes[writes.len() - 2..], [[0x29], [0x20]]);
+        assert!(!display.inverted());
+    }
+
+    #[test]
+    fn present_sends_bands_of_changed_rows() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 1, 5).unwrap();
+        display.sleeping = false;
+        let old = [0u8; 10];
+        let new = [0, 0, 1, 1, 2, 2, 0, 0, 3, 3];
+
+        display.present(&new, &old).unwrap();
+        let writes = writes.borrow();
+        assert_eq!(writes.len(), 12);
+        assert_eq!(writes[3], [0, 1, 0, 2]);
+        assert_eq!(writes[5], [1, 1, 2, 2]);
+        assert_eq!(writes[9], [0, 4, 0, 4]);
+        assert_eq!(writes[11], [3, 3]);
+
+        assert_eq!(display.present(&new[..8], &old), Err(Error::BufferTooSmall));
+    }
+
+    #[test]
+    fn set_refresh_order_sets_ml_and_mh() {
+        let (mut display, writes) = display();
+        display.set_refresh_order(true, false).unwrap();
+        display.set_refresh_order(true, true).unwrap();
+        display.set_orientation(&Orientation::Landscape).unwrap();
+
+        // Before init, MADCTL is at its reset value, so the BGR bit only comes in with
+        // the orientation
+        assert_eq!(
+            writes.borrow().concat(),
+            [0x36, 0x10, 0x36, 0x14, 0x36, 0x7C]
+        );
+    }
+
+    #[test]
+    fn set_refresh_order_after_init_keeps_the_init_rotation() {
+        let (mut display, _writes) = display();
+        display.init(&mut NoopDelay).unwrap();
+        display.set_refresh_order(false, true).unwrap();
+        assert_eq!(display.madctl(), MADCTL_MY | MADCTL_MH | MADCTL_BGR);
+        display.set_refresh_order(true, false).unwrap();
+        assert_eq!(display.madctl(), MADCTL_MY | MADCTL_ML | MADCTL_BGR);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn draw_image_raw_clips_at_the_display_edge() {
+        use embedded_graphics_core::image::ImageDrawable;
+        use embedded_graphics_core::pixelcolor::Rgb565;
+        use embedded_graphics_core::prelude::*;
+        use embedded_graphics_core::primitives::Rectangle;
+
+        struct TwoPixels;
+
+        impl OriginDimensions for TwoPixels {
+            fn size(&self) -> Size {
+                Size::new(2, 1)
+            }
+        }
+
+        impl ImageDrawable for TwoPixels {
+            type Color = Rgb565;
+
+            fn draw<D: DrawTarget<Color = Rgb565>>(&self, target: &mut D) -> Result<(), D::Error> {
+                target.fill_contiguous(&self.bounding_box(), [Rgb565::RED, Rgb565::BLUE])
+            }
+
+            fn draw_sub_image<D: DrawTarget<Color = Rgb565>>(
+                &self,
+                target: &mut D,
+                _area: &Rectangle,
+            ) -> Result<(), D::Error> {
+                self.draw(target)
+            }
+        }
+
+        let (mut display, writes) = display();
+        display
+            .draw_image_raw(&TwoPixels, Point::new(10, 20))
+            .unwrap();
+        assert_eq!(
+            writes.borrow().concat(),
+            [0x2A, 0, 10, 0, 11, 0x2B, 0, 20, 0, 20, 0x2C, 0xF8, 0x00, 0x00, 0x1F]
+        );
+
+        writes.borrow_mut().clear();
+        display
+            .draw_image_raw(&TwoPixels, Point::new(239, 0))
+            .unwrap();
+        assert_eq!(
+            writes.borrow().concat(),
+            [0x2A, 0, 239, 0, 239, 0x2B, 0, 0, 0, 0, 0x2C, 0xF8, 0x00]
+        );
+    }
+
+    /// Interface whose data writes fail with a bus error a set number of times.
+    struct FlakyInterface {
+        failures: u8,
+        attempts: u8,
+    }
+
+    impl DisplayInterface for FlakyInterface {
+        type PinError = Infallible;
+        type BusError = ();
+
+        fn write_command(
+            &mut self,
+            _command: u8,
+            _params: &[u8],
+        ) -> Result<(), Error<Infallible, ()>> {
+            Ok(())
+        }
+
+        fn write_data(&mut self, _data: &[u8]) -> Result<(), Error<Infallible, ()>> {
+            self.attempts += 1;
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(Error::Spi(()));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn with_retries_reissues_failed_transfers() {
+        let interface = FlakyInterface {
+            failures: 2,
+            attempts: 0,
+        };
+        let mut display = GC9A01A::with_interface(interface, NoopPin, false, 240, 240).unwrap();
+        assert_eq!(display.write_data(&[0]), Err(Error::Spi(())));
+        assert_eq!(display.interface.attempts, 1);
+
+        let mut display = display.with_retries(2);
+        display.interface.failures = 2;
+        display.interface.attempts = 0;
+        assert_eq!(display.write_data(&[0]), Ok(()));
+        assert_eq!(display.interface.attempts, 3);
+
+        display.interface.failures = 3;
+        display.interface.attempts = 0;
+        assert_eq!(display.write_data(&[0]), Err(Error::Spi(())));
+        assert_eq!(display.interface.attempts, 3);
     }
 
-    /// Clears the screen by filling it with a single color.
-    ///
-    /// This function sets the entire display to the specified color by writing data
-    /// in chunks, which balances memory efficiency and performance.
-    ///
-    /// # Arguments
-    ///
-    /// * `color` - The color to fill the screen with, in RGB565 format.
-    ///
-    /// # Returns
-    ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn clear_screen(&mut self, color: u16) -> Result<(), ()> {
-        let color_high = (color >> 8) as u8;
-        let color_low = (color & 0xff) as u8;
+    #[test]
+    fn fill_window_sends_the_color_in_pre_filled_chunks() {
+        let (mut display, writes) = display();
+        display.fill_window(10, 20, 39, 39, 0x1234u16).unwrap();
 
-        // Set the address window to cover the entire screen
-        self.set_address_window(0, 0, self.width as u16 - 1, self.height as u16 - 1)?;
-        self.write_command(Instruction::RamWr as u8, &[])?;
-        self.start_data()?;
+        let writes = writes.borrow();
+        assert_eq!(
+            writes[..5].concat(),
+            [0x2A, 0, 10, 0, 39, 0x2B, 0, 20, 0, 39, 0x2C]
+        );
+        assert_eq!(writes[5].len(), 512 * 2);
+        assert_eq!(writes[6].len(), 88 * 2);
+        assert_eq!(writes[5..].concat(), [0x12, 0x34].repeat(30 * 20));
+        assert_eq!(
+            display.fill_window(10, 0, 9, 0, 0u16),
+            Err(Error::OutOfBounds)
+        );
+    }
 
-        // Define a constant for the chunk size
-        const CHUNK_SIZE: usize = 512;
-        let mut chunk = [0u8; CHUNK_SIZE * 2];
+    #[test]
+    fn power_state_follows_sleep_and_display_commands() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 240, 240).unwrap();
+        assert_eq!(display.power_state(), PowerState::Sleeping);
+        assert_eq!(display.sleep_in(&mut NoopDelay), Err(Error::InvalidState));
 
-        // Fill the chunk with the color data
-        for i in 0..CHUNK_SIZE {
-            chunk[i * 2] = color_high;
-            chunk[i * 2 + 1] = color_low;
-        }
+        display.init(&mut NoopDelay).unwrap();
+        assert_eq!(display.power_state(), PowerState::DisplayOn);
+        assert_eq!(display.sleep_out(&mut NoopDelay), Err(Error::InvalidState));
 
-        // Write data in chunks
-        let total_pixels = (self.width * self.height) as usize;
-        let full_chunks = total_pixels / CHUNK_SIZE;
-        let remaining_pixels = total_pixels % CHUNK_SIZE;
+        // Redundant display on and off calls send nothing
+        writes.borrow_mut().clear();
+        display.display_on().unwrap();
+        assert!(writes.borrow().is_empty());
+        display.display_off().unwrap();
+        assert_eq!(display.power_state(), PowerState::DisplayOff);
+        display.display_off().unwrap();
+        assert_eq!(*writes.borrow(), [[0x28]]);
 
-        for _ in 0..full_chunks {
-            self.write_data(&chunk)?;
-        }
+        display.sleep_in(&mut NoopDelay).unwrap();
+        assert!(display.is_sleeping());
+        display.send_command(0x11, &[]).unwrap();
+        assert_eq!(display.power_state(), PowerState::DisplayOff);
 
-        if remaining_pixels > 0 {
-            self.write_data(&chunk[0..(remaining_pixels * 2)])?;
-        }
+        writes.borrow_mut().clear();
+        display.soft_reset(&mut NoopDelay).unwrap();
+        assert_eq!(display.power_state(), PowerState::Sleeping);
+    }
 
-        Ok(())
+    #[test]
+    fn drawing_while_asleep_returns_invalid_state() {
+        let (mut display, writes) = display();
+        display.sleep_in(&mut NoopDelay).unwrap();
+        assert_eq!(
+            display.fill_window(0, 0, 9, 9, 0xFFFFu16),
+            Err(Error::InvalidState)
+        );
+        assert!(!writes.borrow().iter().any(|write| write[..] == [0x2C]));
+
+        display.sleep_out(&mut NoopDelay).unwrap();
+        assert_eq!(display.fill_window(0, 0, 9, 9, 0xFFFFu16), Ok(()));
     }
 
-    /// Sets a pixel color at the given coordinates.
-    ///
-    /// This function sets the color of a single pixel at the specified coordinates.
-    ///
-    /// # Arguments
-    ///
-    /// * `x` - X-coordinate.
-    /// * `y` - Y-coordinate.
-    /// * `color` - Color of the pixel.
-    ///
-    /// # Returns
-    ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn write_pixel(&mut self, x: u16, y: u16, color: u16) -> Result<(), ()> {
-        self.set_address_window(x, y, x, y)?;
-        self.write_command(Instruction::RamWr as u8, &[])?;
-        self.start_data()?;
-        self.write_word(color)
+    #[test]
+    fn sleep_out_waits_out_a_sleep_in_sent_without_its_delay() {
+        /// Delay provider that records each requested delay.
+        struct RecordingDelay(Vec<u32>);
+
+        impl DelayNs for RecordingDelay {
+            fn delay_ns(&mut self, ns: u32) {
+                self.0.push(ns);
+            }
+        }
+
+        let (mut display, _writes) = display();
+        let mut delay = RecordingDelay(Vec::new());
+        display.sleep_in(&mut delay).unwrap();
+        delay.0.clear();
+        display.sleep_out(&mut delay).unwrap();
+        assert_eq!(delay.0, [120_000_000]);
+
+        // SLPIN sent directly gets its 120ms before SLPOUT
+        delay.0.clear();
+        display.send_command(0x10, &[]).unwrap();
+        display.sleep_out(&mut delay).unwrap();
+        assert_eq!(delay.0, [120_000_000, 120_000_000]);
+        assert!(!display.is_sleeping());
     }
 
-    /// Draws an image from a slice of RGB565 data.
-    ///
-    /// This function draws an image from a slice of pixel data in RGB565 format.
-    /// It assumes the image dimensions match the display dimensions.
-    ///
-    /// # Arguments
-    ///
-    /// * `image_data` - Image data to draw.
-    ///
-    /// # Returns
-    ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn draw_image(&mut self, image_data: &[u8]) -> Result<(), ()> {
-        let width = self.width as u16;
-        let height = self.height as u16;
+    #[test]
+    fn self_test_color_bars_cover_the_display() {
+        let (mut display, writes) = display();
+        display.self_test(TestPattern::ColorBars).unwrap();
 
-        self.set_address_window(0, 0, width - 1, height - 1)?;
-        self.write_command(Instruction::RamWr as u8, &[])?;
-        self.start_data()?;
+        let writes = writes.borrow();
+        let windows: Vec<Vec<u8>> = writes
+            .iter()
+            .enumerate()
+            .filter(|(_, write)| write[..] == [0x2A])
+            .map(|(index, _)| writes[index + 1].clone())
+            .collect();
+        assert_eq!(windows.len(), 8);
+        assert_eq!(windows[0], [0, 0, 0, 29]);
+        assert_eq!(windows[7], [0, 210, 0, 239]);
+        let pixel_bytes: usize = writes
+            .iter()
+            .filter(|write| write.len() > 4)
+            .map(Vec::len)
+            .sum();
+        assert_eq!(pixel_bytes, 240 * 240 * 2);
+    }
 
-        for chunk in image_data.chunks(32) {
-            self.write_data(chunk)?;
+    #[test]
+    fn draw_image_rle_expands_runs() {
+        let (mut display, writes) = display();
+        let mut data = Vec::new();
+        for _ in 0..240 * 240 / 200 {
+            data.extend_from_slice(&[200, 0x12, 0x34]);
         }
+        data.extend_from_slice(&[0, 0xFF, 0xFF]);
 
-        Ok(())
+        assert_eq!(
+            display.draw_image_rle(&data[..data.len() - 4]),
+            Err(Error::WrongPixelCount)
+        );
+        assert!(writes.borrow().is_empty());
+
+        display.draw_image_rle(&data).unwrap();
+        let writes = writes.borrow();
+        assert_eq!(writes[4], [0x2C]);
+        assert_eq!(writes[5..].concat(), [0x12, 0x34].repeat(240 * 240));
     }
 
-    /// Displays the provided buffer on the screen.
-    ///
-    /// This function writes the entire buffer to the display, assuming the buffer
-    /// contains pixel data for the full display area.
-    ///
-    /// # Arguments
-    ///
-    /// * `buffer` - Buffer to display.
-    ///
-    /// # Returns
-    ///
-    /// `Result<(), ()>` indicating success or failure.
-    pub fn show(&mut self, buffer: &[u8]) -> Result<(), ()> {
-        self.write_command(Instruction::CaSet as u8, &[])?;
-        self.write_data(&[0x00, 0x00, 0x00, 0xEF])?;
+    #[test]
+    fn with_dma_buffer_sets_the_transfer_size() {
+        let (display, writes) = display();
+        let buffer = std::vec![0u8; 8].leak();
+        let mut display = display.with_dma_buffer(buffer);
 
-        self.write_command(Instruction::RaSet as u8, &[])?;
-        self.write_data(&[0x00, 0x00, 0x00, 0xEF])?;
+        display.write_colors_buffered([0x1234u16; 5]).unwrap();
+        assert_eq!(
+            writes.borrow().iter().map(Vec::len).collect::<Vec<_>>(),
+            [8, 2]
+        );
 
-        self.write_command(Instruction::RamWr as u8, &[])?;
+        writes.borrow_mut().clear();
+        display.fill_window(0, 0, 4, 0, 0x5678u16).unwrap();
+        let writes = writes.borrow();
+        assert_eq!(writes[5..].iter().map(Vec::len).collect::<Vec<_>>(), [8, 2]);
+        assert_eq!(writes[5..].concat(), [0x56, 0x78].repeat(5));
+    }
 
-        self.cs.set_high().map_err(|_| ())?;
-        self.dc.set_high().map_err(|_| ())?;
-        self.cs.set_low().map_err(|_| ())?;
-        self.spi.write(buffer).map_err(|_| ())?;
-        self.cs.set_high().map_err(|_| ())?;
+    #[test]
+    fn write_pixel_skips_the_window_for_the_next_pixel_in_the_row() {
+        let (mut display, writes) = display();
+        display.write_pixel(238, 4, 0x1111u16).unwrap();
+        display.write_pixel(239, 4, 0x2222u16).unwrap();
+        assert_eq!(writes.borrow().len(), 7);
+        assert_eq!(writes.borrow()[6], [0x22, 0x22]);
 
-        Ok(())
+        // The end of the row and any other transfer break the run
+        writes.borrow_mut().clear();
+        display.write_pixel(240, 4, 0x3333u16).unwrap_err();
+        display.write_pixel(0, 5, 0x3333u16).unwrap();
+        display.nop().unwrap();
+        display.write_pixel(1, 5, 0x4444u16).unwrap();
+        let writes = writes.borrow();
+        assert_eq!(writes.len(), 6 + 1 + 6);
+        assert_eq!(writes[7..9].concat(), [0x2A, 0, 1, 0, 239]);
     }
 
-    /// Updates only the specified region of the display with the provided buffer.
-    ///
-    /// This function updates a specified rectangular region of the display with the pixel data
-    /// provided in the buffer. It calculates the necessary offsets and addresses to update only
-    /// the designated area, ensuring efficient display refresh.
-    ///
-    /// # Arguments
-    ///
-    /// * `buffer` - A slice of bytes representing the pixel data in RGB565 format.
-    /// * `top_left_x` - The x-coordinate of the top-left corner of the region to update.
-    /// * `top_left_y` - The y-coordinate of the top-left corner of the region to update.
-    /// * `width` - The width of the region to update.
-    /// * `height` - The height of the region to update.
-    ///
-    /// # Returns
-    ///
-    /// `Result<(), ()>` indicating success (`Ok`) or failure (`Err`).
-    pub fn show_region(
-        &mut self,
-        buffer: &[u8],
-        top_left_x: u16,
-        top_left_y: u16,
-        width: u32,
-        height: u32,
-    ) -> Result<(), ()> {
-        let start_x = top_left_x as u16; // Start x-coordinate
-        let start_y = top_left_y as u16; // Start y-coordinate
-        let end_x = (top_left_x as u32 + width - 1) as u16; // End x-coordinate
-        let end_y = (top_left_y as u32 + height - 1) as u16; // End y-coordinate
+    #[test]
+    fn set_madctl_keeps_the_higher_level_state_in_sync() {
+        let (mut display, writes) = display();
+        display.set_orientation(&Orientation::Landscape).unwrap();
+        assert_eq!(display.madctl(), 0x68);
 
-        // Calculate the buffer offset for the region
-        let buffer_width = self.width as usize; // Width of the buffer
-        let bytes_per_pixel = 2; // Number of bytes per pixel in RGB565 format
+        // Landscape with the column order reversed, RGB and reversed line refresh
+        display.set_madctl(0x30).unwrap();
+        assert_eq!(display.madctl(), 0x30);
+        assert_eq!(display.orientation(), Some(Orientation::Landscape));
+        display.set_color_order(true).unwrap();
+        assert_eq!(display.madctl(), 0x30);
 
-        // Set the address window for the region to be updated
-        self.set_address_window(start_x, start_y, end_x, end_y)?;
+        display.set_madctl(0x88).unwrap();
+        assert_eq!(display.orientation(), Some(Orientation::Portrait));
+        display.set_mirror(false, false).unwrap();
+        assert_eq!(display.madctl(), 0x08);
+        assert_eq!(
+            writes.borrow().concat(),
+            [0x36, 0x68, 0x36, 0x30, 0x36, 0x30, 0x36, 0x88, 0x36, 0x08]
+        );
+    }
 
-        // Send the command to write to RAM
-        self.write_command(Instruction::RamWr as u8, &[])?;
+    #[test]
+    fn verify_color_format_compares_the_read_back_format() {
+        let spi = RecordingSpi {
+            response: std::vec![0x02, 0x80],
+            ..Default::default()
+        };
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 240, 240).unwrap();
 
-        // Start data transmission
-        self.start_data()?;
+        assert_eq!(display.verify_color_format(), Ok(()));
+        assert_eq!(writes.borrow().concat(), [0x0C]);
+        display.color_format = ColorFormat::Rgb666;
+        assert_eq!(
+            display.verify_color_format(),
+            Err(Error::ColorFormatMismatch)
+        );
+    }
 
-        // Iterate over each row in the region
-        for y in start_y..=end_y {
-            let start_index = ((y as usize) * buffer_width + (start_x as usize)) * bytes_per_pixel;
-            let end_index = start_index + (width as usize) * bytes_per_pixel;
+    #[test]
+    fn draw_hline_and_vline_are_clipped_single_window_fills() {
+        let (mut display, writes) = display();
+        display.draw_hline(230, 5, 20, 0xFFFFu16).unwrap();
+        display.draw_vline(7, 235, 20, 0xFFFFu16).unwrap();
+        display.draw_hline(240, 5, 20, 0xFFFFu16).unwrap();
 
-            // Write data to the display in chunks of 32 bytes
-            for chunk in buffer[start_index..end_index].chunks(32) {
-                self.write_data(chunk)?;
-            }
-        }
+        let writes = writes.borrow();
+        assert_eq!(
+            writes[..5].concat(),
+            [0x2A, 0, 230, 0, 239, 0x2B, 0, 5, 0, 5, 0x2C]
+        );
+        assert_eq!(writes[5], [0xFF; 10 * 2]);
+        assert_eq!(
+            writes[6..11].concat(),
+            [0x2A, 0, 7, 0, 7, 0x2B, 0, 235, 0, 239, 0x2C]
+        );
+        assert_eq!(writes[11], [0xFF; 5 * 2]);
+        assert_eq!(writes.len(), 12);
+    }
 
-        Ok(())
+    #[test]
+    fn continue_pixels_sends_only_pixel_data() {
+        let (mut display, writes) = display();
+        display.set_pixels(0, 0, 1, 1, [0x1111u16; 2]).unwrap();
+        display.continue_pixels([0x2222u16; 2]).unwrap();
+
+        let writes = writes.borrow();
+        assert_eq!(writes.len(), 7);
+        assert_eq!(
+            writes[5..].concat(),
+            [0x11, 0x11, 0x11, 0x11, 0x22, 0x22, 0x22, 0x22]
+        );
     }
 
-    pub fn store_region(&mut self, region: Region) -> Result<(), ()> {
-        for i in 0..self.regions.len() {
-            if self.regions[i].is_none() {
-                self.regions[i] = Some(region);
-                return Ok(());
-            }
+    #[test]
+    fn start_write_holds_cs_until_the_guard_ends() {
+        let (mut display, log) = event_display();
+        let mut guard = display.start_write().unwrap();
+        guard.write_pixel(1, 2, 0xFFFFu16).unwrap();
+        guard.nop().unwrap();
+        guard.end_write().unwrap();
+
+        {
+            let mut guard = display.start_write().unwrap();
+            guard.nop().unwrap();
         }
-        Err(())
+
+        let mut cs: Vec<bool> = log
+            .borrow()
+            .iter()
+            .filter_map(|event| match event {
+                Event::Cs(level) => Some(*level),
+                _ => None,
+            })
+            .collect();
+        cs.dedup();
+        assert_eq!(cs, [false, true, false, true]);
     }
 
-    pub fn store_region_from_params(
-        &mut self,
-        x: u16,
-        y: u16,
-        width: u32,
-        height: u32,
-    ) -> Result<(), ()> {
-        let region = Region {
-            x,
-            y,
-            width,
-            height,
-        };
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn flush_framebuffer_sends_only_the_dirty_area() {
+        use embedded_graphics_core::pixelcolor::Rgb565;
+        use embedded_graphics_core::prelude::*;
+        use embedded_graphics_core::primitives::Rectangle;
 
-        self.store_region(region)
+        let (mut display, writes) = display();
+        let mut buffer = std::vec![0; 240 * 240 * 2];
+        let mut framebuffer = FrameBuffer::new(&mut buffer, 240, 240);
+        display.flush_framebuffer(&mut framebuffer).unwrap();
+        assert!(writes.borrow().is_empty());
+
+        framebuffer
+            .draw_iter([
+                Pixel(Point::new(10, 20), Rgb565::RED),
+                Pixel(Point::new(12, 21), Rgb565::RED),
+                Pixel(Point::new(300, 21), Rgb565::RED),
+            ])
+            .unwrap();
+        assert_eq!(
+            framebuffer.dirty_area(),
+            Some(Rectangle::new(Point::new(10, 20), Size::new(3, 2)))
+        );
+
+        display.flush_framebuffer(&mut framebuffer).unwrap();
+        assert_eq!(framebuffer.dirty_area(), None);
+        let writes = writes.borrow();
+        assert_eq!(
+            writes[..5].concat(),
+            [0x2A, 0, 10, 0, 12, 0x2B, 0, 20, 0, 21, 0x2C]
+        );
+        assert_eq!(writes[5..].concat().len(), 3 * 2 * 2);
     }
 
-    pub fn get_regions(&self) -> &[Option<Region>] {
-        &self.regions
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn flush_framebuffer_converts_pixels_in_other_color_formats() {
+        use embedded_graphics_core::pixelcolor::Rgb565;
+        use embedded_graphics_core::prelude::*;
+
+        let (mut display, writes) = display();
+        display.set_color_format(ColorFormat::Rgb666).unwrap();
+        let mut buffer = std::vec![0; 240 * 240 * 2];
+        let mut framebuffer = FrameBuffer::new(&mut buffer, 240, 240);
+        framebuffer
+            .draw_iter([
+                Pixel(Point::new(10, 20), Rgb565::RED),
+                Pixel(Point::new(11, 21), Rgb565::BLUE),
+            ])
+            .unwrap();
+
+        writes.borrow_mut().clear();
+        display.flush_framebuffer(&mut framebuffer).unwrap();
+        let writes = writes.borrow();
+        assert_eq!(
+            writes[..5].concat(),
+            [0x2A, 0, 10, 0, 11, 0x2B, 0, 20, 0, 21, 0x2C]
+        );
+        assert_eq!(
+            writes[5..].concat(),
+            [0xFC, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xFC]
+        );
     }
 
-    pub fn clear_regions(&mut self) {
-        self.regions = [None; 10];
+    #[test]
+    fn reset_address_window_covers_the_display_with_its_offset() {
+        let spi = RecordingSpi::default();
+        let writes = spi.writes.clone();
+        let mut display: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 200, 220).unwrap();
+        display.sleeping = false;
+        display.set_offset(20, 10);
+        display.write_pixel(3, 4, 0u16).unwrap();
+
+        writes.borrow_mut().clear();
+        display.reset_address_window().unwrap();
+        assert_eq!(
+            writes.borrow().concat(),
+            [0x2A, 0, 20, 0, 219, 0x2B, 0, 10, 0, 229]
+        );
     }
 
-    pub fn show_regions(&mut self, buffer: &[u8]) -> Result<(), ()> {
-        for i in 0..self.regions.len() {
-            if self.regions[i].is_some() {
-                if let Some(region_data) = self.regions[i] {
-                    self.show_region(
-                        buffer,
-                        region_data.x,
-                        region_data.y,
-                        region_data.width,
-                        region_data.height,
-                    )?;
-                }
-            }
-        }
+    #[test]
+    fn text_console_wraps_at_the_right_edge() {
+        use core::fmt::Write;
 
-        Ok(())
+        let (mut display, _writes) = display();
+        let mut console = TextConsole::new(&mut display, 0xFFFFu16, 0).unwrap();
+        write!(console, "{}", "x".repeat(41)).unwrap();
+        assert_eq!(console.cursor(), (1, 1));
+
+        console.write_text("\n\r").unwrap();
+        assert_eq!(console.cursor(), (0, 2));
     }
 
-    // Additional function with default parameter
-    pub fn show_regions_and_clear(&mut self, buffer: &[u8]) -> Result<(), ()> {
-        if let Err(e) = self.show_regions(buffer) {
-            // Handle the error, e.g., log it or return a different error
-            return Err(e);
+    #[test]
+    fn text_console_scrolls_and_redraws_only_changed_cells() {
+        let (mut display, writes) = display();
+        let mut console = TextConsole::new(&mut display, 0xFFFFu16, 0)
+            .unwrap()
+            .with_area(0, 0, 2, 2)
+            .unwrap();
+        console.write_text("ab\ncd\nef").unwrap();
+        assert_eq!(console.cursor(), (2, 1));
+
+        // Four glyphs, four changed cells when scrolling and two more glyphs
+        let ramwrs = writes
+            .borrow()
+            .iter()
+            .filter(|write| write[..] == [0x2C])
+            .count();
+        assert_eq!(ramwrs, 10);
+    }
+
+    #[test]
+    fn text_console_rejects_areas_that_do_not_fit() {
+        let (mut display, _writes) = display();
+        // 10923 columns of 6 pixels would wrap to 2 pixels in u16 arithmetic
+        for (x, columns, rows) in [(0, 0, 1), (0, 1, 0), (235, 1, 1), (0, 10923, 1)] {
+            let console = TextConsole::new(&mut display, 0xFFFFu16, 0)
+                .unwrap()
+                .with_area(x, 0, columns, rows);
+            assert!(matches!(console, Err(Error::OutOfBounds)));
         }
-        self.clear_regions();
-        Ok(())
+
+        let spi = RecordingSpi::default();
+        let mut narrow: TestDisplay =
+            GC9A01A::new(spi, NoopPin, NoopPin, NoopPin, false, 5, 240).unwrap();
+        let console = TextConsole::new(&mut narrow, 0xFFFFu16, 0);
+        assert!(matches!(console, Err(Error::OutOfBounds)));
     }
-}
 
-// Implementing the DrawTarget trait for the GC9A01A display driver
-impl<SPI, DC, CS, RST> DrawTarget for GC9A01A<SPI, DC, CS, RST>
-where
-    SPI: Write<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
-{
-    type Color = Rgb565;
-    type Error = ();
+    #[test]
+    fn init_waits_after_commands_that_need_a_delay() {
+        /// Delay provider that records each requested delay.
+        struct RecordingDelay(Vec<u32>);
 
-    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
-    where
-        I: IntoIterator<Item = Pixel<Self::Color>>,
-    {
-        for Pixel(coord, color) in pixels.into_iter() {
-            let color_value = color.into_storage();
-            // Only draw pixels that would be on screen
-            if coord.x >= 0
-                && coord.y >= 0
-                && coord.x < self.width as i32
-                && coord.y < self.height as i32
-            {
-                self.write_pixel(coord.x as u16, coord.y as u16, color_value)?;
+        impl DelayNs for RecordingDelay {
+            fn delay_ns(&mut self, ns: u32) {
+                self.0.push(ns);
             }
         }
-        Ok(())
+
+        let (mut display, _writes) = display();
+        let mut delay = RecordingDelay(Vec::new());
+        display.init(&mut delay).unwrap();
+        // Reset pulse and settle, then SLPOUT and DISPON
+        assert_eq!(
+            delay.0,
+            [10_000, 10_000, 120_000_000, 120_000_000, 20_000_000]
+        );
     }
-}
 
-// Implementing the OriginDimensions trait for the GC9A01A display driver
-impl<SPI, DC, CS, RST> OriginDimensions for GC9A01A<SPI, DC, CS, RST>
-where
-    SPI: Write<u8>,
-    DC: OutputPin,
-    CS: OutputPin,
-    RST: OutputPin,
-{
-    fn size(&self) -> Size {
-        Size::new(self.width, self.height)
+    #[test]
+    fn reinit_restores_the_orientation_and_power_state() {
+        let (mut display, writes) = display();
+        display.init(&mut NoopDelay).unwrap();
+        display.set_orientation(&Orientation::Landscape).unwrap();
+        let madctl = display.madctl();
+        display.sleep_in(&mut NoopDelay).unwrap();
+
+        writes.borrow_mut().clear();
+        display.reinit(&mut NoopDelay).unwrap();
+        assert_eq!(display.power_state(), PowerState::DisplayOn);
+        assert_eq!(display.madctl(), madctl);
+        let writes = writes.borrow();
+        assert_eq!(
+            writes[writes.len() - 2..],
+            [std::vec![0x36], std::vec![madctl]]
+        );
     }
-}
 
-/// A structure representing a frame buffer.
-pub struct FrameBuffer<'a> {
-    buffer: &'a mut [u8],
-    width: u32,
-    height: u32,
-}
+    #[test]
+    fn write_pixels_counted_reports_the_pixels_sent() {
+        /// Interface whose data writes start failing after a set number succeed.
+        struct FailingInterface {
+            successes: u8,
+        }
 
-impl<'a> FrameBuffer<'a> {
-    /// Creates a new frame buffer.
-    ///
-    /// # Arguments
-    ///
-    /// * `buffer` - A mutable slice representing the pixel data.
-    /// * `width` - The width of the frame buffer.
-    /// * `height` - The height of the frame buffer.
-    pub fn new(buffer: &'a mut [u8], width: u32, height: u32) -> Self {
-        Self {
-            buffer,
-            width,
-            height,
+        impl DisplayInterface for FailingInterface {
+            type PinError = Infallible;
+            type BusError = ();
+
+            fn write_command(
+                &mut self,
+                _command: u8,
+                _params: &[u8],
+            ) -> Result<(), Error<Infallible, ()>> {
+                Ok(())
+            }
+
+            fn write_data(&mut self, _data: &[u8]) -> Result<(), Error<Infallible, ()>> {
+                if self.successes == 0 {
+                    return Err(Error::Spi(()));
+                }
+                self.successes -= 1;
+                Ok(())
+            }
         }
-    }
 
-    /// Returns a reference to the buffer.
-    ///
-    /// # Returns
-    ///
-    /// A reference to the buffer.
-    pub fn get_buffer(&self) -> &[u8] {
-        self.buffer
+        let (mut display, _writes) = display();
+        assert_eq!(display.write_pixels_counted([0xFFFFu16; 20]), Ok(20));
+
+        // The first transfer holds 16 pixels and the second one fails
+        let interface = FailingInterface { successes: 1 };
+        let mut display = GC9A01A::with_interface(interface, NoopPin, false, 240, 240).unwrap();
+        display.sleeping = false;
+        assert_eq!(
+            display.write_pixels_counted([0xFFFFu16; 20]),
+            Err((16, Error::Spi(())))
+        );
     }
 
-    /// Clears the frame buffer with the specified color.
-    ///
-    /// # Arguments
-    ///
-    /// * `color` - The color to clear the buffer with.
-    pub fn clear(&mut self, color: Rgb565) {
-        let raw_color = color.into_storage();
-        for chunk in self.buffer.chunks_exact_mut(2) {
-            chunk[0] = (raw_color >> 8) as u8;
-            chunk[1] = raw_color as u8;
+    #[test]
+    fn dimensions_must_be_non_empty_and_fit_the_frame_memory() {
+        let display = GC9A01A::builder(RecordingSpi::default(), NoopPin, NoopPin, NoopPin)
+            .dimensions(200, 200)
+            .resolution(Resolution::R240x240)
+            .build()
+            .unwrap();
+        assert_eq!(display.dimensions(), (240, 240));
+
+        let oversized = GC9A01A::builder(RecordingSpi::default(), NoopPin, NoopPin, NoopPin)
+            .dimensions(320, 240)
+            .build();
+        assert!(matches!(oversized, Err(Error::OutOfBounds)));
+
+        for (width, height) in [(0, 240), (240, 0), (200, 480)] {
+            let (spi, dc, cs) = (RecordingSpi::default(), NoopPin, NoopPin);
+            let display = GC9A01A::new(spi, dc, cs, NoopPin, false, width, height);
+            assert!(matches!(display, Err(Error::OutOfBounds)));
         }
     }
 
-    /// Copies a region from another buffer into this buffer.
-    ///
-    /// # Arguments
-    ///
-    /// * `src_buffer` - The source buffer.
-    /// * `src_x` - The x-coordinate of the top-left corner of the source region.
-    /// * `src_y` - The y-coordinate of the top-left corner of the source region.
-    /// * `src_width` - The width of the source region.
-    /// * `src_height` - The height of the source region.
-    /// * `dest_x` - The x-coordinate of the top-left corner of the destination region.
-    /// * `dest_y` - The y-coordinate of the top-left corner of the destination region.
-    pub fn copy_region(
-        &mut self,
-        src_buffer: &[u8],
-        src_x: u16,
-        src_y: u16,
-        src_width: u32,
-        src_height: u32,
-        dest_x: u16,
-        dest_y: u16,
-    ) {
-        for row in 0..src_height as usize {
-            let src_row_start =
-                (src_y as usize + row) * self.width as usize * 2 + src_x as usize * 2;
-            let src_row_end = src_row_start + src_width as usize * 2;
+    #[test]
+    fn set_rows_clips_each_row() {
+        let (mut display, writes) = display();
+        let rows: [&[u16]; 3] = [&[1, 2, 3], &[4], &[5, 6, 7]];
+        display
+            .set_rows(237, 238, 4, rows.map(|row| row.iter().copied()))
+            .unwrap();
 
-            let dest_row_start =
-                (dest_y as usize + row) * self.width as usize * 2 + dest_x as usize * 2;
-            let dest_row_end = dest_row_start + src_width as usize * 2;
+        // Three pixels fit across and two rows down; the short row stays short
+        let writes = writes.borrow();
+        assert_eq!(writes.len(), 12);
+        assert_eq!(writes[1], [0, 237, 0, 239]);
+        assert_eq!(writes[3], [0, 238, 0, 238]);
+        assert_eq!(writes[5], [0, 1, 0, 2, 0, 3]);
+        assert_eq!(writes[9], [0, 239, 0, 239]);
+        assert_eq!(writes[11], [0, 4]);
+    }
 
-            self.buffer[dest_row_start..dest_row_end]
-                .copy_from_slice(&src_buffer[src_row_start..src_row_end]);
+    #[test]
+    fn retries_start_on_a_fresh_cs_cycle() {
+        /// SPI bus whose writes fail a set number of times, logging the others.
+        struct FailingSpi {
+            log: EventLog,
+            failures: Rc<core::cell::Cell<u8>>,
         }
-    }
 
-    /// Restores regions from a source buffer into the frame buffer.
-    ///
-    /// # Arguments
-    ///
-    /// * `src_buffer` - The source buffer.
-    /// * `regions` - An array of regions to restore.
-    pub fn copy_regions(&mut self, src_buffer: &[u8], regions: &[Option<Region>]) {
-        for region in regions.iter().flatten() {
-            self.copy_region(
-                src_buffer,
-                region.x,
-                region.y,
-                region.width,
-                region.height,
-                region.x,
-                region.y,
-            );
+        impl embedded_hal::spi::ErrorType for FailingSpi {
+            type Error = embedded_hal::spi::ErrorKind;
         }
-    }
 
-    /// Compares the current frame buffer with another frame buffer and returns an iterator
-    /// of `Pixel` that can be drawn to update the display.
-    ///
-    /// # Arguments
-    ///
-    /// * `other` - The other frame buffer to compare against.
-    ///
-    /// # Returns
-    ///
-    /// An iterator of `Pixel<Rgb565>`.
-    pub fn diff_with<'b>(&'b self, other: &'b FrameBuffer<'a>) -> impl Iterator<Item = Pixel<Rgb565>> + 'b {
-        self.buffer.chunks_exact(2).enumerate().filter_map(move |(i, chunk)| {
-            let other_chunk = &other.buffer[i * 2..i * 2 + 2];
-            if chunk != other_chunk {
-                let x = (i as u32 % self.width) as i32;
-                let y = (i as u32 / self.width) as i32;
-                let raw_color = u16::from_be_bytes([chunk[0], chunk[1]]);
-                let color = Rgb565::from(RawU16::new(raw_color));
-                Some(Pixel(Point::new(x, y), color))
-            } else {
-                None
+        impl SpiBus<u8> for FailingSpi {
+            fn read(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+                Ok(())
             }
-        })
+
+            fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+                if self.failures.get() > 0 {
+                    self.failures.set(self.failures.get() - 1);
+                    return Err(embedded_hal::spi::ErrorKind::Other);
+                }
+                self.log.borrow_mut().push(Event::Write(words.to_vec()));
+                Ok(())
+            }
+
+            fn transfer(&mut self, _read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+                self.write(write)
+            }
+
+            fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+                Ok(())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        let log = EventLog::default();
+        let failures = Rc::new(core::cell::Cell::new(1));
+        let spi = FailingSpi {
+            log: log.clone(),
+            failures: failures.clone(),
+        };
+        let interface = SpiInterface::new(
+            spi,
+            EventPin(log.clone(), Event::Dc),
+            EventPin(log.clone(), Event::Cs),
+        );
+        let mut display = GC9A01A::with_interface(interface, NoopPin, false, 240, 240)
+            .unwrap()
+            .with_retries(1);
+        display.write_data(&[1, 2]).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            [
+                Event::Dc(true),
+                Event::Cs(false),
+                Event::Cs(true),
+                Event::Dc(true),
+                Event::Cs(false),
+                Event::Write(std::vec![1, 2]),
+                Event::Cs(true),
+            ]
+        );
+
+        // Inside a held transaction CS still gets an edge, then stays low
+        log.borrow_mut().clear();
+        let mut guard = display.start_write().unwrap();
+        failures.set(1);
+        guard.write_data(&[3]).unwrap();
+        guard.end_write().unwrap();
+        assert_eq!(
+            *log.borrow(),
+            [
+                Event::Cs(false),
+                Event::Dc(true),
+                Event::Cs(false),
+                Event::Cs(true),
+                Event::Cs(false),
+                Event::Dc(true),
+                Event::Cs(false),
+                Event::Write(std::vec![3]),
+                Event::Cs(true),
+            ]
+        );
     }
-}
 
-impl<'a> DrawTarget for FrameBuffer<'a> {
-    type Color = Rgb565;
-    type Error = ();
+    #[test]
+    fn wait_for_tearing_effect_waits_for_a_rising_edge_or_times_out() {
+        /// Input pin that plays back a sequence of levels, then repeats the last.
+        struct ScriptedPin(Vec<bool>);
 
-    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
-    where
-        I: IntoIterator<Item = Pixel<Self::Color>>,
-    {
-        for Pixel(coord, color) in pixels {
-            if coord.x >= 0
-                && coord.x < self.width as i32
-                && coord.y >= 0
-                && coord.y < self.height as i32
-            {
-                let index = ((coord.y as u32 * self.width + coord.x as u32) * 2) as usize;
-                let raw_color = color.into_storage();
-                self.buffer[index] = (raw_color >> 8) as u8;
-                self.buffer[index + 1] = raw_color as u8;
+        impl embedded_hal::digital::ErrorType for ScriptedPin {
+            type Error = Infallible;
+        }
+
+        impl InputPin for ScriptedPin {
+            fn is_high(&mut self) -> Result<bool, Infallible> {
+                let level = self.0[0];
+                if self.0.len() > 1 {
+                    self.0.remove(0);
+                }
+                Ok(level)
+            }
+
+            fn is_low(&mut self) -> Result<bool, Infallible> {
+                self.is_high().map(|high| !high)
             }
         }
-        Ok(())
+
+        let (mut display, _writes) = display();
+        let mut te = ScriptedPin(std::vec![true, false, false, true]);
+        assert_eq!(
+            display.wait_for_tearing_effect(&mut te, &mut NoopDelay, 1),
+            Ok(())
+        );
+
+        let mut stuck = ScriptedPin(std::vec![false]);
+        assert_eq!(
+            display.wait_for_tearing_effect(&mut stuck, &mut NoopDelay, 1),
+            Err(Error::Timeout)
+        );
     }
-}
 
-impl<'a> OriginDimensions for FrameBuffer<'a> {
-    fn size(&self) -> Size {
-        Size::new(self.width, self.height)
+    #[test]
+    fn from_preset_applies_the_module_configuration() {
+        let mut display = GC9A01A::from_preset(
+            RecordingSpi::default(),
+            NoopPin,
+            NoopPin,
+            NoopPin,
+            ModulePreset::GenericRound240,
+        )
+        .unwrap();
+        assert_eq!(display.dimensions(), (240, 240));
+        assert_eq!(display.offset(), (0, 0));
+
+        // BGR color order sets the BGR bit of MADCTL
+        display.init(&mut NoopDelay).unwrap();
+        assert_eq!(display.madctl() & MADCTL_BGR, MADCTL_BGR);
+    }
+
+    #[test]
+    fn set_gamma_with_the_default_tables_repeats_the_init_sequence() {
+        let gamma = [
+            Instruction::Gamma1,
+            Instruction::Gamma2,
+            Instruction::Gamma3,
+            Instruction::Gamma4,
+        ]
+        .map(|register| {
+            INIT_SEQUENCE
+                .iter()
+                .find(|(command, _)| *command == register as u8)
+                .unwrap()
+                .1
+        });
+        assert_eq!(
+            gamma,
+            [&DEFAULT_GAMMA[..6], &DEFAULT_GAMMA[6..]].repeat(2)[..]
+        );
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,224 @@
+//! Filled shapes and lines drawn with window fills.
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{Color, DisplayInterface, Error, GC9A01A};
+
+impl<DI, RST, BL, const BUF: usize> GC9A01A<DI, RST, BL, BUF>
+where
+    DI: DisplayInterface,
+    RST: OutputPin<Error = DI::PinError>,
+{
+    /// Fills a rectangle with a single color.
+    ///
+    /// The color is converted once and sent from a pre-filled chunk, as with
+    /// `fill_window`. Parts of the rectangle that extend past the display edges are
+    /// clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the top-left corner.
+    /// * `y` - Y-coordinate of the top-left corner.
+    /// * `width` - Width of the rectangle.
+    /// * `height` - Height of the rectangle.
+    /// * `color` - Fill color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn fill_rect<C: Color>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u32,
+        height: u32,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        if x as u32 >= self.width || y as u32 >= self.height || width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let width = width.min(self.width - x as u32);
+        let height = height.min(self.height - y as u32);
+        let end_x = (x as u32 + width - 1) as u16;
+        let end_y = (y as u32 + height - 1) as u16;
+
+        self.fill_window(x, y, end_x, end_y, color)
+    }
+
+    /// Fills a rectangle that may extend past any edge of the display.
+    ///
+    /// Like `fill_rect`, but the top-left corner may be negative, so shapes can be
+    /// drawn partly off the left or top edge. Only the visible part is filled.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the top-left corner.
+    /// * `y` - Y-coordinate of the top-left corner.
+    /// * `width` - Width of the rectangle.
+    /// * `height` - Height of the rectangle.
+    /// * `color` - Fill color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Nothing is sent if no part
+    /// of the rectangle is visible.
+    pub fn fill_rect_clipped<C: Color>(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let end_x = (x as i64 + width as i64).min(self.width as i64);
+        let end_y = (y as i64 + height as i64).min(self.height as i64);
+        let (x, y) = (x.max(0) as i64, y.max(0) as i64);
+        if x >= end_x || y >= end_y {
+            return Ok(());
+        }
+
+        self.fill_rect(
+            x as u16,
+            y as u16,
+            (end_x - x) as u32,
+            (end_y - y) as u32,
+            color,
+        )
+    }
+
+    /// Draws a horizontal line.
+    ///
+    /// The line is sent as a single one-row window fill and clipped at the right
+    /// edge of the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the left end.
+    /// * `y` - Y-coordinate of the line.
+    /// * `length` - Length of the line in pixels.
+    /// * `color` - Line color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Nothing is sent if the
+    /// line starts outside the display.
+    pub fn draw_hline<C: Color>(
+        &mut self,
+        x: u16,
+        y: u16,
+        length: u32,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.fill_rect(x, y, length, 1, color)
+    }
+
+    /// Draws a vertical line.
+    ///
+    /// The line is sent as a single one-column window fill and clipped at the
+    /// bottom edge of the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X-coordinate of the line.
+    /// * `y` - Y-coordinate of the top end.
+    /// * `length` - Length of the line in pixels.
+    /// * `color` - Line color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure. Nothing is sent if the
+    /// line starts outside the display.
+    pub fn draw_vline<C: Color>(
+        &mut self,
+        x: u16,
+        y: u16,
+        length: u32,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.fill_rect(x, y, 1, length, color)
+    }
+
+    /// Fills a circle with a single color.
+    ///
+    /// Each row of the circle is sent as one span, so no pixel is addressed on its
+    /// own. Parts of the circle outside the display are clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - X-coordinate of the center.
+    /// * `cy` - Y-coordinate of the center.
+    /// * `radius` - Radius of the circle.
+    /// * `color` - Fill color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn fill_circle<C: Color>(
+        &mut self,
+        cx: u16,
+        cy: u16,
+        radius: u16,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        self.draw_ring(cx, cy, 0, radius, color)
+    }
+
+    /// Fills the ring between two concentric circles with a single color.
+    ///
+    /// Pixels further than `inner_radius` and at most `outer_radius` from the center
+    /// are filled, which suits gauge faces and bezels. Each row is sent as at most
+    /// two spans, and parts of the ring outside the display are clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - X-coordinate of the center.
+    /// * `cy` - Y-coordinate of the center.
+    /// * `inner_radius` - Radius of the hole, or 0 for a filled circle.
+    /// * `outer_radius` - Outer radius of the ring.
+    /// * `color` - Fill color.
+    ///
+    /// # Returns
+    ///
+    /// `Result<(), Error>` indicating success or failure.
+    pub fn draw_ring<C: Color>(
+        &mut self,
+        cx: u16,
+        cy: u16,
+        inner_radius: u16,
+        outer_radius: u16,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let (cx, cy) = (cx as i32, cy as i32);
+        let outer_squared = outer_radius as i32 * outer_radius as i32;
+        let inner_squared = inner_radius as i32 * inner_radius as i32;
+
+        for dy in -(outer_radius as i32)..=outer_radius as i32 {
+            let outer_half = (outer_squared - dy * dy).isqrt();
+            if inner_radius == 0 || dy.abs() > inner_radius as i32 {
+                self.fill_span(cx - outer_half, cx + outer_half, cy + dy, color)?;
+            } else {
+                let inner_half = (inner_squared - dy * dy).isqrt();
+                self.fill_span(cx - outer_half, cx - inner_half - 1, cy + dy, color)?;
+                self.fill_span(cx + inner_half + 1, cx + outer_half, cy + dy, color)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fills the columns `start..=end` of a row, clipped to the display.
+    fn fill_span<C: Color>(
+        &mut self,
+        start: i32,
+        end: i32,
+        y: i32,
+        color: C,
+    ) -> Result<(), Error<DI::PinError, DI::BusError>> {
+        let start = start.max(0);
+        if end < start || y < 0 || y >= self.height as i32 || start >= self.width as i32 {
+            return Ok(());
+        }
+        self.fill_rect(start as u16, y as u16, (end - start + 1) as u32, 1, color)
+    }
+}